@@ -1,2 +1,10 @@
-pub const NS_MS_ASM_V1: &'static str = "urn:schemas-microsoft-com:asm.v1";
-pub const NS_MS_COMPAT_V1: &'static str = "urn:schemas-microsoft-com:compatibility.v1";
+pub const NS_MS_ASM_V1: &str = "urn:schemas-microsoft-com:asm.v1";
+pub const NS_MS_ASM_V2: &str = "urn:schemas-microsoft-com:asm.v2";
+pub const NS_MS_ASM_V3: &str = "urn:schemas-microsoft-com:asm.v3";
+pub const NS_MS_COMPAT_V1: &str = "urn:schemas-microsoft-com:compatibility.v1";
+pub const NS_SMI_2005: &str = "http://schemas.microsoft.com/SMI/2005/WindowsSettings";
+pub const NS_SMI_2011: &str = "http://schemas.microsoft.com/SMI/2011/WindowsSettings";
+pub const NS_SMI_2016: &str = "http://schemas.microsoft.com/SMI/2016/WindowsSettings";
+pub const NS_SMI_2017: &str = "http://schemas.microsoft.com/SMI/2017/WindowsSettings";
+pub const NS_SMI_2019: &str = "http://schemas.microsoft.com/SMI/2019/WindowsSettings";
+pub const NS_SMI_2020: &str = "http://schemas.microsoft.com/SMI/2020/WindowsSettings";
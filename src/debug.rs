@@ -1,8 +1,11 @@
 use std::fmt::{Display, Formatter};
 
+/// Segment of [`Path`]
 #[derive(Debug, Copy, Clone)]
 pub enum PathNode {
+    /// Name of field
     Named(&'static str),
+    /// Index of list
     Indexed(usize),
 }
 
@@ -27,24 +30,27 @@ impl Display for PathNode {
     }
 }
 
-#[derive(Debug)]
+/// Path of value from manifest root, displayed as `.files.0.name`
+#[derive(Debug, Copy, Clone)]
 pub struct Path<'a>(Option<&'a Path<'a>>, PathNode);
 
 impl<'a> Display for Path<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(ancestors) = &self.0 {
-            write!(f, "{}.", ancestors)?;
+            write!(f, "{}", ancestors)?;
         }
         write!(f, ".{}", &self.1)
     }
 }
 
 impl<'a> Path<'a> {
+    /// Path of field of manifest root
     pub fn new(node: PathNode) -> Path<'a> {
         Path(None, node)
     }
 
+    /// Path of child value
     pub fn appended<'b: 'a>(&'b self, node: PathNode) -> Path<'b> {
-        Path(Some(&self), node)
+        Path(Some(self), node)
     }
 }
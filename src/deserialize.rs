@@ -0,0 +1,253 @@
+use crate::debug::Path;
+use crate::xml_alias::XmlName;
+use std::io::Read;
+use xml::{
+    attribute::OwnedAttribute,
+    common::{Position, TextPosition},
+    name::OwnedName,
+    namespace::{Namespace, NS_EMPTY_URI, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
+    reader::{Error as XmlError, ParserConfig, XmlEvent},
+};
+
+/// Error can be occured while parsing.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    /// Error from XmlReader
+    #[error("XmlRead failed - {0}")]
+    XmlReadError(#[from] XmlError),
+    /// Manifest is invalid.
+    #[error("Invalid data found at {path} ({line}:{column}). {detail}")]
+    Invalid {
+        /// Path of invalid value from manifest root
+        path: String,
+        /// 1-based line number of the element containing invalid value
+        line: u64,
+        /// 1-based column number of the element containing invalid value
+        column: u64,
+        /// Detailed reason. It can be a hint to fix error.
+        detail: String,
+    },
+}
+
+/// Parsing result
+pub type ParseResult<R> = std::result::Result<R, ParseError>;
+
+pub trait DeserializableElement: Sized {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self>;
+}
+
+pub trait DeserializableValue: Sized {
+    fn deserialize(value: &str) -> Option<Self>;
+}
+
+/// Node of parsed XML document
+#[derive(Debug)]
+pub enum Node {
+    Element(Element),
+    Characters(String),
+    Comment(String),
+}
+
+/// Element of parsed XML document
+#[derive(Debug)]
+pub struct Element {
+    pub name: OwnedName,
+    pub attributes: Vec<OwnedAttribute>,
+    /// Namespaces declared on this element, pairs of prefix and URI
+    pub namespaces: Vec<(String, String)>,
+    pub children: Vec<Node>,
+    pub position: TextPosition,
+}
+
+/// Parsed XML document
+#[derive(Debug)]
+pub struct Document {
+    pub root: Element,
+    /// Comments outside of root element, and whether each one follows root element
+    pub comments: Vec<(bool, String)>,
+}
+
+impl Element {
+    /// Read document, keeping comments outside of root element
+    pub fn read_document<R: Read>(reader: R) -> ParseResult<Document> {
+        let mut reader = ParserConfig::new()
+            .trim_whitespace(true)
+            .cdata_to_characters(true)
+            .ignore_comments(false)
+            .create_reader(reader);
+        let mut stack = Vec::<(Element, Namespace)>::new();
+        let mut root = None;
+        let mut comments = Vec::new();
+
+        loop {
+            let event = reader.next()?;
+            let position = reader.position();
+            match event {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } => {
+                    let namespaces = namespace
+                        .iter()
+                        .filter(|(prefix, uri)| {
+                            let inherited = match stack.last() {
+                                Some((_, parent)) => parent.get(*prefix),
+                                None if *prefix == NS_NO_PREFIX => Some(NS_EMPTY_URI),
+                                None => None,
+                            };
+                            *prefix != NS_XML_PREFIX
+                                && *prefix != NS_XMLNS_PREFIX
+                                && inherited != Some(*uri)
+                        })
+                        .map(|(prefix, uri)| (prefix.to_string(), uri.to_string()))
+                        .collect();
+                    stack.push((
+                        Element {
+                            name,
+                            attributes,
+                            namespaces,
+                            children: Vec::new(),
+                            position,
+                        },
+                        namespace,
+                    ));
+                }
+                XmlEvent::EndElement { .. } => {
+                    let (element, _) = stack.pop().unwrap();
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.children.push(Node::Element(element)),
+                        None => root = Some(element),
+                    }
+                }
+                XmlEvent::Characters(characters) => {
+                    if let Some((parent, _)) = stack.last_mut() {
+                        parent.children.push(Node::Characters(characters));
+                    }
+                }
+                XmlEvent::Comment(comment) => match stack.last_mut() {
+                    Some((parent, _)) => parent.children.push(Node::Comment(comment)),
+                    None => comments.push((root.is_some(), comment)),
+                },
+                XmlEvent::EndDocument => {
+                    return Ok(Document {
+                        root: root.unwrap(),
+                        comments,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn is(&self, name: &XmlName<'_>) -> bool {
+        self.name.local_name == name.local_name && self.name.namespace.as_deref() == name.namespace
+    }
+
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|child| match child {
+            Node::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Characters(characters) => Some(characters.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn attribute(&self, local_name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|attribute| {
+                attribute.name.namespace.is_none() && attribute.name.local_name == local_name
+            })
+            .map(|attribute| attribute.value.as_str())
+    }
+
+    pub fn invalid<S: Into<String>>(&self, path: &Path<'_>, detail: S) -> ParseError {
+        ParseError::Invalid {
+            path: path.to_string(),
+            line: self.position.row + 1,
+            column: self.position.column + 1,
+            detail: detail.into(),
+        }
+    }
+
+    /// Parse attribute value
+    pub fn value<T: DeserializableValue>(
+        &self,
+        local_name: &str,
+        path: &Path<'_>,
+    ) -> ParseResult<Option<T>> {
+        match self.attribute(local_name) {
+            Some(value) => T::deserialize(value).map(Some).ok_or_else(|| {
+                self.invalid(
+                    path,
+                    format!("unexpected value of {} - {}", local_name, value),
+                )
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse required attribute value
+    pub fn required_value<T: DeserializableValue>(
+        &self,
+        local_name: &str,
+        path: &Path<'_>,
+    ) -> ParseResult<T> {
+        self.value(local_name, path)?.ok_or_else(|| {
+            self.invalid(
+                path,
+                format!("{} requires {} attribute", self.name.local_name, local_name),
+            )
+        })
+    }
+
+    /// Parse text content
+    pub fn text_value<T: DeserializableValue>(&self, path: &Path<'_>) -> ParseResult<T> {
+        let text = self.text();
+        T::deserialize(text.trim()).ok_or_else(|| {
+            self.invalid(
+                path,
+                format!("unexpected value of {} - {}", self.name.local_name, text),
+            )
+        })
+    }
+}
+
+impl DeserializableValue for String {
+    fn deserialize(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl DeserializableValue for bool {
+    fn deserialize(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("true") {
+            Some(true)
+        } else if value.eq_ignore_ascii_case("false") {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+impl DeserializableValue for u32 {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+impl DeserializableValue for u64 {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
@@ -0,0 +1,249 @@
+//! Embedding manifest from build scripts
+//!
+//! Manifest is written to `OUT_DIR`, and linked with `cargo:rustc-link-arg` directives.
+//!
+//! - `*-windows-msvc`: linker embeds manifest with `/MANIFEST:EMBED /MANIFESTINPUT:<path>`,
+//!   adding `/MANIFESTUAC:NO` when manifest has its own requested execution level
+//! - `*-windows-gnu` and `*-windows-gnullvm`: manifest is compiled to resource object with windres,
+//!   and the object is linked
+//! - other targets: nothing is done
+//!
+//! ```no_run
+//! // in build.rs
+//! let manifest = sxs_manifest::AssemblyManifest::default();
+//! sxs_manifest::embed::embed(&manifest).unwrap();
+//! ```
+use crate::{
+    error::SerializeError,
+    resource::{ManifestKind, ResourceScript},
+    AssemblyManifest,
+};
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Error of embedding manifest
+#[derive(Debug, thiserror::Error)]
+pub enum EmbedError {
+    /// Environment variable set by Cargo is missing
+    #[error("environment variable {0} is not set")]
+    MissingEnv(&'static str),
+    #[allow(missing_docs)]
+    #[error("failed to serialize manifest - {0}")]
+    Serialize(#[from] SerializeError),
+    #[allow(missing_docs)]
+    #[error("failed to write {} - {source}", path.display())]
+    Io {
+        #[allow(missing_docs)]
+        path: PathBuf,
+        #[allow(missing_docs)]
+        source: io::Error,
+    },
+    /// Resource compiler can't be run, or failed
+    #[error("failed to run {} - {detail}", program.to_string_lossy())]
+    Windres {
+        #[allow(missing_docs)]
+        program: OsString,
+        #[allow(missing_docs)]
+        detail: String,
+    },
+}
+
+/// How manifest is embedded for target
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Method {
+    /// Manifest file is passed to MSVC-compatible linker
+    MsvcLinker,
+    /// Manifest is compiled to resource object with windres
+    Windres,
+    /// Target doesn't use manifest
+    None,
+}
+
+impl Method {
+    /// Method for target triple, such as `x86_64-pc-windows-msvc`
+    pub fn for_target(target: &str) -> Method {
+        let mut parts = target.rsplitn(2, '-');
+        let env = parts.next().unwrap_or_default();
+        let os = parts.next().unwrap_or_default();
+        if !os.ends_with("-windows") {
+            return Method::None;
+        }
+        match env {
+            "msvc" => Method::MsvcLinker,
+            "gnu" | "gnullvm" => Method::Windres,
+            _ => Method::None,
+        }
+    }
+}
+
+/// Embedding settings
+#[derive(Debug, Clone)]
+pub struct Embed {
+    target: String,
+    out_dir: PathBuf,
+    bin: Option<String>,
+    kind: ManifestKind,
+    windres: Option<OsString>,
+}
+
+impl Embed {
+    /// Embed into binaries of `target`, writing files to `out_dir`
+    pub fn new<S: Into<String>, P: Into<PathBuf>>(target: S, out_dir: P) -> Self {
+        Embed {
+            target: target.into(),
+            out_dir: out_dir.into(),
+            bin: None,
+            kind: ManifestKind::Executable,
+            windres: None,
+        }
+    }
+
+    /// Settings of current build, from `TARGET`, `OUT_DIR` and `WINDRES`.
+    pub fn from_env() -> Result<Self, EmbedError> {
+        let env = |name| std::env::var_os(name).ok_or(EmbedError::MissingEnv(name));
+        let mut embed = Embed::new(
+            env("TARGET")?.to_string_lossy().into_owned(),
+            env("OUT_DIR")?,
+        );
+        embed.windres = std::env::var_os("WINDRES");
+        Ok(embed)
+    }
+
+    /// Embed only into the binary `name`, with `cargo:rustc-link-arg-bin`.
+    ///
+    /// Manifests of multiple binaries can be embedded by calling [`Embed::write`] for each binary.
+    pub fn bin<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.bin = Some(name.into());
+        self
+    }
+
+    /// Kind of binaries, such as [`ManifestKind::IsolationAwareDll`] for `cdylib`
+    pub fn kind(&mut self, kind: ManifestKind) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Resource compiler for [`Method::Windres`].
+    ///
+    /// Default is `llvm-windres` for `gnullvm`,
+    /// `windres` on Windows hosts and `<arch>-w64-mingw32-windres` on others.
+    pub fn windres<S: Into<OsString>>(&mut self, program: S) -> &mut Self {
+        self.windres = Some(program.into());
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn method(&self) -> Method {
+        Method::for_target(&self.target)
+    }
+
+    /// Write files for embedding `manifest`, and return cargo directives to print.
+    pub fn write(&self, manifest: &AssemblyManifest) -> Result<Vec<String>, EmbedError> {
+        let args = match self.method() {
+            Method::MsvcLinker => {
+                let mut args = vec![
+                    match self.kind {
+                        ManifestKind::Executable => "/MANIFEST:EMBED".to_string(),
+                        kind => format!("/MANIFEST:EMBED,ID={}", kind.resource_id()),
+                    },
+                    format!(
+                        "/MANIFESTINPUT:{}",
+                        self.write_manifest(manifest)?.display()
+                    ),
+                ];
+                // otherwise linker adds `trustInfo` which conflicts with the one of manifest
+                if manifest.trust_info.requested_execution_level.is_some() {
+                    args.push("/MANIFESTUAC:NO".to_string());
+                }
+                args
+            }
+            Method::Windres => {
+                let manifest_path = self.write_manifest(manifest)?;
+                vec![self.compile_resource(&manifest_path)?.display().to_string()]
+            }
+            Method::None => return Ok(Vec::new()),
+        };
+
+        Ok(args
+            .into_iter()
+            .map(|arg| match &self.bin {
+                Some(bin) => format!("cargo:rustc-link-arg-bin={}={}", bin, arg),
+                None => format!("cargo:rustc-link-arg={}", arg),
+            })
+            .collect())
+    }
+
+    fn write_manifest(&self, manifest: &AssemblyManifest) -> Result<PathBuf, EmbedError> {
+        let path = self.out_path("manifest.xml");
+        let file = std::fs::File::create(&path).map_err(|source| EmbedError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        manifest.serialize(file)?;
+        Ok(path)
+    }
+
+    fn out_path(&self, name: &str) -> PathBuf {
+        match &self.bin {
+            Some(bin) => self.out_dir.join(format!("{}.{}", bin, name)),
+            None => self.out_dir.join(name),
+        }
+    }
+
+    fn default_windres(&self) -> OsString {
+        let arch = self.target.split('-').next().unwrap_or_default();
+        if self.target.ends_with("-gnullvm") {
+            "llvm-windres".into()
+        } else if cfg!(windows) {
+            "windres".into()
+        } else {
+            format!("{}-w64-mingw32-windres", arch).into()
+        }
+    }
+
+    fn compile_resource(&self, manifest_path: &Path) -> Result<PathBuf, EmbedError> {
+        let rc_path = self.out_path("manifest.rc");
+        let object_path = self.out_path("manifest.o");
+        let mut rc = ResourceScript::new(manifest_path);
+        rc.kind(self.kind);
+        std::fs::write(&rc_path, rc.to_string()).map_err(|source| EmbedError::Io {
+            path: rc_path.clone(),
+            source,
+        })?;
+
+        let program = self
+            .windres
+            .clone()
+            .unwrap_or_else(|| self.default_windres());
+        let failed = |detail: String| EmbedError::Windres {
+            program: program.clone(),
+            detail,
+        };
+        let status = Command::new(&program)
+            .arg("--input")
+            .arg(&rc_path)
+            .arg("--output-format=coff")
+            .arg("--output")
+            .arg(&object_path)
+            .status()
+            .map_err(|err| failed(err.to_string()))?;
+        if !status.success() {
+            return Err(failed(status.to_string()));
+        }
+        Ok(object_path)
+    }
+}
+
+/// Embed manifest into binaries of current build, and print cargo directives.
+///
+/// Use [`Embed`] to embed into specific binary or to change resource compiler.
+pub fn embed(manifest: &AssemblyManifest) -> Result<(), EmbedError> {
+    for line in Embed::from_env()?.write(manifest)? {
+        println!("{}", line);
+    }
+    Ok(())
+}
@@ -13,9 +13,26 @@
 //! # }
 //! ```
 //!
+//! # Serde
+//!
+//! With `serde` feature, the manifest model implements `Serialize` and `Deserialize`.
+//! Fields are named as in Rust, and missing ones take their default values.
+//! Values are represented as they are written in XML:
+//!
+//! - `AssemblyVersion` as `"1.2.3.4"`
+//! - `PublicKeyToken` as uppercase hex, such as `"6595B64144CCF1DF"`
+//! - `ProcessArchitecture` as `"x86"`, `"amd64"`, `"arm64"`, `"*"` etc.
+//! - `Guid` as `"{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}"`
+//! - `SupportedOS` by name, one of `"Windows10"`, `"Windows8.1"`, `"Windows8"`, `"Windows7"`
+//!   and `"WindowsVista"`, or GUID when custom. Other names accepted by `FromStr`, such as `"win11"`, can be read too.
+//!
+//! Unknown content and extensions are not serialized.
+//!
+//! With `cargo-metadata` feature, manifest can be loaded from `Cargo.toml` with [`metadata`](metadata/index.html).
+//!
 #![deny(missing_docs)]
 
-use std::io::Write;
+use std::{io::Write, str::FromStr};
 
 /// XML writer config.
 /// Re-exported from xml-rs.
@@ -23,9 +40,17 @@ pub use xml::writer::EmitterConfig;
 
 mod consts;
 mod debug;
+mod deserialize;
+pub mod embed;
+pub mod lint;
 /// Detailed types of manifest
 pub mod manifest;
+#[cfg(feature = "cargo-metadata")]
+pub mod metadata;
+pub mod resource;
+pub mod schema;
 mod serialize;
+mod validate;
 mod xml_alias {
     pub use xml::{
         attribute::Attribute as XmlAttribute,
@@ -35,12 +60,24 @@ mod xml_alias {
     };
 }
 
+use deserialize::{ParseError, ParseResult};
 pub use manifest::AssemblyManifest;
 use serialize::SerializeResult;
 
+/// Traits for writing elements which are not modeled,
+/// with [`Extension`](manifest/enum.Extension.html)
+pub mod element {
+    pub use crate::debug::{Path, PathNode};
+    pub use crate::serialize::{SerializableElement, SerializableValue, XmlWriter};
+    /// Re-exported from xml-rs.
+    pub use xml::writer::XmlEvent;
+}
+
 #[allow(missing_docs)]
 pub mod error {
+    pub use crate::deserialize::{ParseError, ParseResult};
     pub use crate::serialize::{SerializeError, SerializeResult};
+    pub use crate::validate::{Problem, Severity};
 }
 
 /// Serialization helper methods
@@ -68,6 +105,15 @@ impl AssemblyManifest {
     }
 }
 
+/// Deserialization helper methods
+impl FromStr for AssemblyManifest {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> ParseResult<Self> {
+        AssemblyManifest::from_reader(s.as_bytes())
+    }
+}
+
 #[test]
 fn test_empty_manifest() {
     let manifest = AssemblyManifest::default();
@@ -101,12 +147,12 @@ fn test_max_version_tested() {
     use manifest::*;
 
     let mut manifest = AssemblyManifest::default();
-    manifest.compatibility.max_version_tested = Some(AssemblyVersion {
+    manifest.compatibility.max_version_tested = vec![AssemblyVersion {
         major: 10,
         minor: 0,
         build: 18358,
         revision: Some(0),
-    });
+    }];
     let mut config = EmitterConfig::new();
     config.indent_string = "".into();
     config.line_separator = "".into();
@@ -121,3 +167,1368 @@ fn test_max_version_tested() {
 
     assert_eq!(serialized, include_str!("tests/max_version_tested.xml"));
 }
+
+#[test]
+fn test_windows_settings() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    manifest.windows_settings.dpi_aware = Some(DpiAware::TruePerMonitor);
+    manifest.windows_settings.dpi_awareness =
+        vec![DpiAwareness::PerMonitorV2, DpiAwareness::PerMonitor];
+    manifest.windows_settings.long_path_aware = Some(true);
+    manifest.windows_settings.printer_driver_isolation = Some(true);
+    manifest.windows_settings.gdi_scaling = Some(false);
+    manifest.windows_settings.active_code_page = Some(ActiveCodePage::Utf8);
+    manifest.windows_settings.heap_type = Some(HeapType::SegmentHeap);
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(serialized, include_str!("tests/windows_settings.xml"));
+
+    manifest
+        .windows_settings
+        .dpi_awareness
+        .push(DpiAwareness::PerMonitor);
+    assert!(manifest.serialize_to_string_with_config(config).is_err());
+}
+
+#[test]
+fn test_trust_info() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    manifest.trust_info.requested_execution_level =
+        Some(RequestedExecutionLevel::new(ExecutionLevel::AsInvoker));
+    manifest.windows_settings.auto_elevate = Some(true);
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    assert!(manifest
+        .serialize_to_string_with_config(config.clone())
+        .is_err());
+    manifest.windows_settings.auto_elevate = None;
+    let serialized = manifest.serialize_to_string_with_config(config).unwrap();
+
+    assert_eq!(serialized, include_str!("tests/trust_info.xml"));
+}
+
+#[test]
+fn test_parse() {
+    use manifest::*;
+
+    let manifest: AssemblyManifest = include_str!("tests/parse.xml").parse().unwrap();
+
+    let mut expected = AssemblyManifest::default();
+    expected.trust_info.requested_execution_level = Some(RequestedExecutionLevel::new(
+        ExecutionLevel::RequireAdministrator,
+    ));
+    expected
+        .compatibility
+        .supported_os
+        .insert(SupportedOS::Windows10);
+    expected
+        .compatibility
+        .supported_os
+        .insert(SupportedOS::Windows7);
+    expected.compatibility.max_version_tested = vec![windows_version::WINDOWS_10_1903];
+    expected.windows_settings.dpi_aware = Some(DpiAware::True);
+    expected.windows_settings.dpi_awareness = vec![DpiAwareness::PerMonitorV2];
+    expected
+        .dependencies
+        .push(Dependency::from(AssemblyIdentity {
+            process_architecture: Some(ProcessArchitecture::X86),
+            version: Some(AssemblyVersion::new(6, 0, 0, Some(0))),
+            public_key_token: Some(PublicKeyToken([
+                0x65, 0x95, 0xb6, 0x41, 0x44, 0xcc, 0xf1, 0xdf,
+            ])),
+            language: Some("*".to_string()),
+            ..AssemblyIdentity::new("Microsoft.Windows.Common-Controls")
+        }));
+    expected.unknown.namespaces.push((
+        "asmv3".to_string(),
+        "urn:schemas-microsoft-com:asm.v3".to_string(),
+    ));
+    assert_eq!(manifest, expected);
+
+    let reparsed: AssemblyManifest = manifest.serialize_to_string().unwrap().parse().unwrap();
+    assert_eq!(reparsed, expected);
+}
+
+#[test]
+fn test_parse_error() {
+    let err = include_str!("tests/parse_error.xml")
+        .parse::<AssemblyManifest>()
+        .unwrap_err();
+
+    match err {
+        ParseError::Invalid {
+            path, line, column, ..
+        } => {
+            assert_eq!(
+                path,
+                ".dependencies.1.dependent_assemblies.0.identity.version"
+            );
+            assert_eq!((line, column), (10, 7));
+        }
+        err => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn test_unknown_round_trip() {
+    use manifest::*;
+
+    let mut manifest: AssemblyManifest = include_str!("tests/unknown.xml").parse().unwrap();
+    manifest.windows_settings.long_path_aware = Some(true);
+    assert_eq!(manifest.document_nodes.len(), 2);
+    assert_eq!(manifest.dependencies.len(), 3);
+    assert_eq!(manifest.dependencies[1].dependent_assemblies.len(), 2);
+    assert_eq!(manifest.dependencies[1].unknown.nodes.len(), 1);
+    assert!(manifest.dependencies[2].dependent_assemblies.is_empty());
+    manifest.dependencies[0].dependent_assemblies[0]
+        .identity
+        .version = Some(AssemblyVersion::new(2, 0, 0, Some(0)));
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest.serialize_to_string_with_config(config).unwrap();
+
+    assert_eq!(serialized, include_str!("tests/unknown_round_trip.xml"));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    manifest.trust_info.requested_execution_level = None;
+    let serialized = manifest.serialize_to_string().unwrap();
+    assert!(serialized.contains("<applicationRequestMinimum>"));
+    assert!(!serialized.contains("<requestedPrivileges"));
+    let reparsed: AssemblyManifest = serialized.parse().unwrap();
+    assert_eq!(reparsed.serialize_to_string().unwrap(), serialized);
+}
+
+#[test]
+fn test_file() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    let mut file = File::new("server.dll");
+    file.hash = Some(vec![0xab; 20]);
+    file.hash_algorithm = Some(HashAlgorithm::Sha1);
+    let mut com_class = ComClass::new(Guid(0x0bb9f9a8_2f5c_4a67_8a3e_9c11f5b8e001));
+    com_class.threading_model = Some(ThreadingModel::Apartment);
+    com_class.progid = Some("Example.Server.1".to_string());
+    com_class.tlbid = Some(Guid(0x5d6c2c0e_3c26_4f0b_9a43_1f0a2cbb7002));
+    com_class.progids.push("Example.Server".to_string());
+    file.com_classes.push(com_class);
+    let mut proxy_stub =
+        ComInterfaceProxyStub::new(Guid(0x9a3f2b1c_7d4e_4c5f_8e6a_0b1c2d3e4003), "IServer");
+    proxy_stub.num_methods = Some(4);
+    file.com_interface_proxy_stubs.push(proxy_stub);
+    let mut typelib = TypeLib::new(
+        Guid(0x5d6c2c0e_3c26_4f0b_9a43_1f0a2cbb7002),
+        TypeLibVersion { major: 1, minor: 0 },
+    );
+    typelib.flags = vec![TypeLibFlag::HasDiskImage];
+    file.typelibs.push(typelib);
+    let mut window_class = WindowClass::new("ExampleWindow");
+    window_class.versioned = Some(false);
+    file.window_classes.push(window_class);
+    manifest.files.push(file);
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(serialized, include_str!("tests/file.xml"));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    let parsed: AssemblyManifest = include_str!("tests/file_unknown.xml").parse().unwrap();
+    let file = &parsed.files[0];
+    assert_eq!(file.com_classes[0].progids, vec!["A.B".to_string()]);
+    assert_eq!(file.com_classes[0].unknown.attributes.len(), 2);
+    assert_eq!(file.com_classes[0].unknown.nodes.len(), 2);
+    assert_eq!(
+        file.com_interface_proxy_stubs[0].unknown.attributes.len(),
+        1
+    );
+    assert_eq!(file.typelibs[0].unknown.attributes.len(), 1);
+    assert_eq!(file.window_classes[0].name, "ExampleWindow");
+    assert_eq!(file.window_classes[0].unknown.nodes.len(), 1);
+    assert_eq!(
+        parsed
+            .serialize_to_string_with_config(config.clone())
+            .unwrap(),
+        include_str!("tests/file_unknown.xml")
+    );
+
+    manifest.files[0].hash_algorithm = Some(HashAlgorithm::Md5);
+    assert!(manifest.serialize_to_string_with_config(config).is_err());
+}
+
+#[test]
+fn test_com_interface_external_proxy_stub() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    let mut proxy_stub =
+        ComInterfaceProxyStub::new(Guid(0x2c0b8f3e_5a1d_4e6f_9b7c_8d9e0f1a2b3c), "IAutomation");
+    proxy_stub.tlbid = Some(Guid(0x5d6c2c0e_3c26_4f0b_9a43_1f0a2cbb7002));
+    proxy_stub.proxy_stub_clsid32 = Some(ComInterfaceProxyStub::OLE_AUTOMATION_PROXY_STUB_CLSID);
+    manifest.com_interface_external_proxy_stubs.push(proxy_stub);
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(
+        serialized,
+        include_str!("tests/com_interface_external_proxy_stub.xml")
+    );
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    let unknown = serialized.replace(
+        " />",
+        " threadingModel=\"Both\"><!-- marshaled with type library --></comInterfaceExternalProxyStub>",
+    );
+    let parsed: AssemblyManifest = unknown.parse().unwrap();
+    assert_eq!(
+        parsed.com_interface_external_proxy_stubs[0]
+            .unknown
+            .attributes
+            .len(),
+        1
+    );
+    assert_eq!(
+        parsed
+            .serialize_to_string_with_config(config.clone())
+            .unwrap(),
+        unknown
+    );
+
+    manifest.com_interface_external_proxy_stubs[0].base_interface =
+        Some(manifest.com_interface_external_proxy_stubs[0].iid);
+    assert!(manifest.serialize_to_string_with_config(config).is_err());
+}
+
+#[test]
+fn test_clr() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    let mut clr_class = ClrClass::new(
+        "Example.Interop.Server",
+        Guid(0x7e1f5a2b_3c4d_4e5f_a6b7_c8d9e0f1a2b3),
+    );
+    clr_class.progid = Some("Example.Interop.Server".to_string());
+    clr_class.runtime_version = Some("v4.0.30319".to_string());
+    clr_class.threading_model = Some(ThreadingModel::Both);
+    manifest.clr_classes.push(clr_class);
+    manifest.clr_surrogates.push(ClrSurrogate::new(
+        Guid(0x1a2b3c4d_5e6f_4a7b_8c9d_0e1f2a3b4c5d),
+        "Example.Interop.Surrogate",
+    ));
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(serialized, include_str!("tests/clr.xml"));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    let parsed: AssemblyManifest = include_str!("tests/clr_unknown.xml").parse().unwrap();
+    assert_eq!(parsed.clr_classes[0].unknown.attributes.len(), 1);
+    assert_eq!(parsed.clr_classes[0].unknown.nodes.len(), 2);
+    assert_eq!(parsed.clr_surrogates[0].unknown.nodes.len(), 1);
+    assert_eq!(
+        parsed
+            .serialize_to_string_with_config(config.clone())
+            .unwrap(),
+        include_str!("tests/clr_unknown.xml")
+    );
+
+    manifest.clr_surrogates[0].runtime_version = Some("4.0".to_string());
+    assert!(manifest.serialize_to_string_with_config(config).is_err());
+}
+
+#[test]
+fn test_dependent_assembly() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    let mut dependent_assembly = DependentAssembly::new(AssemblyIdentity {
+        version: Some(AssemblyVersion::new(2, 0, 0, Some(0))),
+        ..AssemblyIdentity::new("Vendor.Runtime")
+    });
+    dependent_assembly.binding_redirects.push(BindingRedirect {
+        old_version: VersionRange::new(
+            AssemblyVersion::new(1, 0, 0, Some(0)),
+            AssemblyVersion::new(1, 9, 9, Some(9)),
+        ),
+        new_version: AssemblyVersion::new(2, 0, 0, Some(0)),
+    });
+    manifest.dependencies.push(Dependency {
+        optional: Some(true),
+        ..dependent_assembly.into()
+    });
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(serialized, include_str!("tests/dependent_assembly.xml"));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    let old_version =
+        &mut manifest.dependencies[0].dependent_assemblies[0].binding_redirects[0].old_version;
+    std::mem::swap(&mut old_version.start, &mut old_version.end);
+    assert!(manifest.serialize_to_string_with_config(config).is_err());
+}
+
+#[test]
+fn test_assembly_identity() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest {
+        assembly_identity: Some(AssemblyIdentity::new("Example.Library")),
+        no_inheritable: true,
+        description: Some("Example library".to_string()),
+        no_inherit: true,
+        ..AssemblyManifest::default()
+    };
+    manifest.trust_info.requested_execution_level =
+        Some(RequestedExecutionLevel::new(ExecutionLevel::AsInvoker));
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    assert!(manifest
+        .serialize_to_string_with_config(config.clone())
+        .is_err());
+    manifest.assembly_identity.as_mut().unwrap().version =
+        Some(AssemblyVersion::new(1, 0, 0, Some(0)));
+    let serialized = manifest.serialize_to_string_with_config(config).unwrap();
+
+    assert_eq!(serialized, include_str!("tests/assembly_identity.xml"));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+}
+
+#[test]
+fn test_process_architecture() {
+    use manifest::*;
+
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("x86_64-pc-windows-msvc"),
+        Some(ProcessArchitecture::X86_64)
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("i686-pc-windows-gnu"),
+        Some(ProcessArchitecture::X86)
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("aarch64-pc-windows-msvc"),
+        Some(ProcessArchitecture::Arm64)
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("thumbv7a-pc-windows-msvc"),
+        Some(ProcessArchitecture::Arm)
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("arm64ec-pc-windows-msvc"),
+        Some(ProcessArchitecture::Arm64)
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_triple("wasm32-unknown-unknown"),
+        None
+    );
+    assert_eq!(
+        ProcessArchitecture::from_target_arch("arm64ec"),
+        Some(ProcessArchitecture::Arm64)
+    );
+    assert_eq!("AMD64".parse(), Ok(ProcessArchitecture::X86_64));
+    assert_eq!("*".parse(), Ok(ProcessArchitecture::Any));
+    assert!("itanium".parse::<ProcessArchitecture>().is_err());
+
+    let mut manifest = AssemblyManifest::default();
+    manifest
+        .dependencies
+        .push(Dependency::from(AssemblyIdentity {
+            process_architecture: Some(ProcessArchitecture::X86_64),
+            ..AssemblyIdentity::new("Microsoft.Windows.Common-Controls")
+        }));
+    manifest
+        .dependencies
+        .push(Dependency::from(AssemblyIdentity {
+            process_architecture: Some(ProcessArchitecture::Any),
+            ..AssemblyIdentity::new("Microsoft.Windows.GdiPlus")
+        }));
+    let serialized = manifest.serialize_to_string().unwrap();
+
+    assert!(serialized.contains(r#"processorArchitecture="amd64""#));
+    assert!(serialized.contains(r#"processorArchitecture="*""#));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+}
+
+#[test]
+fn test_assembly_version() {
+    use error::SerializeError;
+    use manifest::*;
+
+    let version: AssemblyVersion = "1.2.3.4".parse().unwrap();
+    assert_eq!(version, AssemblyVersion::new(1, 2, 3, Some(4)));
+    assert_eq!(version.to_string(), "1.2.3.4");
+    assert!("1.2.3.65536".parse::<AssemblyVersion>().is_err());
+    assert!("1.2".parse::<AssemblyVersion>().is_err());
+    assert!(version < AssemblyVersion::new(1, 10, 0, Some(0)));
+    let without_revision: AssemblyVersion = "1.2.3".parse().unwrap();
+    assert_eq!(without_revision, AssemblyVersion::new(1, 2, 3, Some(0)));
+    assert!(without_revision < version);
+    assert_eq!(
+        without_revision.to_string().parse::<AssemblyVersion>(),
+        Ok(without_revision)
+    );
+    let mut manifest = AssemblyManifest::default();
+    let compatibility = &mut manifest.compatibility;
+    compatibility.supported_os.insert(SupportedOS::Windows10);
+    compatibility.max_version_tested = vec![without_revision, "1.2.3.0".parse().unwrap()];
+    assert_eq!(manifest.validate()[0].code, "duplicate-max-version-tested");
+    assert_eq!(
+        AssemblyVersion::from_semver("0.3.1").unwrap(),
+        AssemblyVersion::new(0, 3, 1, Some(65535))
+    );
+    assert_eq!(
+        AssemblyVersion::from_semver("1.0.0-beta.7+build.5").unwrap(),
+        AssemblyVersion::new(1, 0, 0, Some(7))
+    );
+    assert_eq!(
+        AssemblyVersion::from_semver("1.0.0-beta").unwrap(),
+        AssemblyVersion::new(1, 0, 0, Some(0))
+    );
+    assert!(
+        AssemblyVersion::from_semver("1.0.0-beta.7").unwrap()
+            < AssemblyVersion::from_semver("1.0.0").unwrap()
+    );
+    assert!(
+        AssemblyVersion::from_semver("0.9.9").unwrap()
+            < AssemblyVersion::from_semver("1.0.0-alpha").unwrap()
+    );
+    assert!(AssemblyVersion::from_semver("1.0.0-rc.65535").is_err());
+    assert!(AssemblyVersion::from_semver(env!("CARGO_PKG_VERSION")).is_ok());
+
+    let mut manifest = AssemblyManifest::default();
+    manifest
+        .dependencies
+        .push(Dependency::from(AssemblyIdentity {
+            version: Some(AssemblyVersion::new(6, 0, 70000, Some(0))),
+            ..AssemblyIdentity::new("Microsoft.Windows.Common-Controls")
+        }));
+    match manifest.serialize_to_string().unwrap_err() {
+        SerializeError::Invalid { problems } => {
+            assert_eq!(
+                problems[0].path,
+                ".dependencies.0.dependent_assemblies.0.identity.version.build"
+            )
+        }
+        err => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn test_public_key_token() {
+    use manifest::*;
+
+    let token: PublicKeyToken = "6595b64144ccf1df".parse().unwrap();
+    assert_eq!(
+        token,
+        PublicKeyToken([0x65, 0x95, 0xb6, 0x41, 0x44, 0xcc, 0xf1, 0xdf])
+    );
+    assert!("6595b64144ccf1".parse::<PublicKeyToken>().is_err());
+
+    // ECMA standard public key
+    assert_eq!(
+        PublicKeyToken::from_public_key_blob(&[0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0]),
+        "b77a5c561934e089".parse().unwrap()
+    );
+
+    let expected: PublicKeyToken = "8A29B662D880968A".parse().unwrap();
+    assert_eq!(
+        PublicKeyToken::from_snk(include_bytes!("tests/key.snk")).unwrap(),
+        expected
+    );
+    assert_eq!(
+        PublicKeyToken::from_x509_der(include_bytes!("tests/cert.der")).unwrap(),
+        expected
+    );
+    assert_eq!(
+        PublicKeyToken::from_x509_der(&include_bytes!("tests/cert.der")[..100]),
+        Err(PublicKeyError::Malformed)
+    );
+}
+
+#[test]
+fn test_supported_os_names() {
+    use manifest::*;
+
+    assert_eq!("win10".parse(), Ok(SupportedOS::Windows10));
+    assert_eq!("Windows 11".parse(), Ok(SupportedOS::WINDOWS_11));
+    assert_eq!("windows8.1".parse(), Ok(SupportedOS::Windows8_1));
+    assert_eq!("vista".parse(), Ok(SupportedOS::WindowsVista));
+    assert_eq!(
+        "{35138B9A-5D96-4FBD-8E2D-A2440225F93A}".parse(),
+        Ok(SupportedOS::Windows7)
+    );
+    assert!("windows95".parse::<SupportedOS>().is_err());
+
+    let custom = SupportedOS::Custom(Guid(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef));
+    assert!(SupportedOS::WindowsVista < SupportedOS::Windows7);
+    assert!(SupportedOS::Windows8_1 < SupportedOS::Windows10);
+    assert!(SupportedOS::Windows10 < custom);
+
+    let mut manifest = AssemblyManifest::default();
+    manifest.compatibility.supported_os.insert(custom);
+    let serialized = manifest.serialize_to_string().unwrap();
+
+    assert!(serialized.contains(r#"Id="{01234567-89ab-cdef-0123-456789abcdef}""#));
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+}
+
+#[test]
+fn test_supported_os_order() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    for os in &[
+        SupportedOS::Windows7,
+        SupportedOS::Windows10,
+        SupportedOS::WindowsVista,
+        SupportedOS::Windows8_1,
+        SupportedOS::Windows8,
+    ] {
+        manifest.compatibility.supported_os.insert(*os);
+    }
+    assert!(!manifest
+        .compatibility
+        .supported_os
+        .insert(SupportedOS::Windows10));
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest
+        .serialize_to_string_with_config(config.clone())
+        .unwrap();
+
+    assert_eq!(serialized, include_str!("tests/supported_os_multiple.xml"));
+
+    manifest.compatibility.supported_os_order = SupportedOSOrder::Insertion;
+    let serialized = manifest.serialize_to_string_with_config(config).unwrap();
+    let ids = serialized
+        .match_indices("Id=\"")
+        .map(|(idx, _)| {
+            serialized[idx + 4..idx + 42]
+                .parse::<SupportedOS>()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        ids,
+        manifest
+            .compatibility
+            .supported_os
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_windows_version_catalogue() {
+    use manifest::windows_version::*;
+    use manifest::*;
+
+    assert_eq!(
+        find(Product::Windows11, "22h2").map(|release| release.version),
+        Some(WINDOWS_11_22H2)
+    );
+    assert_eq!(by_name("22H2").count(), 2);
+    assert_eq!(
+        by_build(17763)
+            .map(|release| release.product)
+            .collect::<Vec<_>>(),
+        vec![Product::Windows10, Product::WindowsServer]
+    );
+    assert_eq!(latest(Product::Windows10).version, WINDOWS_10_22H2);
+    assert!(latest_known().version >= WINDOWS_11_24H2);
+
+    let mut manifest = AssemblyManifest::default();
+    manifest
+        .compatibility
+        .supported_os
+        .insert(SupportedOS::Windows10);
+    manifest.compatibility.max_version_tested = vec![WINDOWS_10_22H2, WINDOWS_11_24H2];
+    let serialized = manifest.serialize_to_string().unwrap();
+
+    assert_eq!(serialized.matches("<maxversiontested ").count(), 2);
+    assert_eq!(serialized.parse::<AssemblyManifest>().unwrap(), manifest);
+
+    manifest
+        .compatibility
+        .max_version_tested
+        .push(WINDOWS_10_22H2);
+    assert!(manifest.serialize_to_string().is_err());
+}
+
+#[test]
+fn test_validate() {
+    use error::{SerializeError, Severity};
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest {
+        no_inheritable: true,
+        ..Default::default()
+    };
+    manifest.trust_info.requested_execution_level = Some(RequestedExecutionLevel {
+        level: ExecutionLevel::AsInvoker,
+        ui_access: Some(true),
+    });
+    manifest.windows_settings.auto_elevate = Some(true);
+    manifest.files.push(File::new(""));
+    manifest
+        .dependencies
+        .push(AssemblyIdentity::new("Microsoft.Windows.Common-Controls").into());
+    assert!(AssemblyManifest::default().validate().is_empty());
+
+    let problems = manifest.validate();
+    let summary = problems
+        .iter()
+        .map(|problem| (problem.severity, problem.code, problem.path.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                Severity::Error,
+                "no-inheritable-without-identity",
+                ".no_inheritable"
+            ),
+            (
+                Severity::Error,
+                "auto-elevate-without-admin",
+                ".windows_settings.auto_elevate"
+            ),
+            (
+                Severity::Warning,
+                "ui-access",
+                ".trust_info.requested_execution_level.ui_access"
+            ),
+            (
+                Severity::Warning,
+                "dependency-without-version",
+                ".dependencies.0.dependent_assemblies.0.identity.version"
+            ),
+            (Severity::Error, "empty-name", ".files.0.name"),
+        ]
+    );
+
+    match manifest.serialize_to_string().unwrap_err() {
+        SerializeError::Invalid { problems } => assert_eq!(
+            problems
+                .iter()
+                .map(|problem| problem.code)
+                .collect::<Vec<_>>(),
+            vec![
+                "no-inheritable-without-identity",
+                "auto-elevate-without-admin",
+                "empty-name"
+            ]
+        ),
+        err => panic!("unexpected error {}", err),
+    }
+    assert_eq!(
+        problems[4].to_string(),
+        "error[empty-name] at .files.0.name: name of file is required (set path of file relative to the manifest)"
+    );
+}
+
+#[test]
+fn test_lint() {
+    use error::Severity;
+    use lint::*;
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest::default();
+    manifest.windows_settings.dpi_aware = Some(DpiAware::False);
+    manifest.windows_settings.dpi_awareness = vec![DpiAwareness::PerMonitorV2];
+    let common_controls = AssemblyIdentity {
+        version: Some(AssemblyVersion::new(6, 0, 0, Some(0))),
+        public_key_token: Some(PublicKeyToken([0; 8])),
+        ..AssemblyIdentity::new("Microsoft.Windows.Common-Controls")
+    };
+    manifest.dependencies = vec![
+        common_controls.clone().into(),
+        AssemblyIdentity {
+            public_key_token: Some("6595b64144ccf1df".parse().unwrap()),
+            ..common_controls
+        }
+        .into(),
+    ];
+    let summary = |problems: Vec<error::Problem>| {
+        problems
+            .into_iter()
+            .map(|problem| (problem.severity, problem.code, problem.path))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        summary(manifest.lint(&LintConfig::new())),
+        vec![
+            (
+                Severity::Warning,
+                DPI_WITHOUT_WINDOWS10.code,
+                ".windows_settings.dpi_awareness".to_string()
+            ),
+            (
+                Severity::Warning,
+                DPI_MISMATCH.code,
+                ".windows_settings.dpi_aware".to_string()
+            ),
+            (
+                Severity::Error,
+                COMMON_CONTROLS_PUBLIC_KEY_TOKEN.code,
+                ".dependencies.0.dependent_assemblies.0.identity.public_key_token".to_string()
+            ),
+            (
+                Severity::Warning,
+                DUPLICATE_DEPENDENCY.code,
+                ".dependencies.1.dependent_assemblies.0.identity.name".to_string()
+            ),
+        ]
+    );
+
+    manifest
+        .compatibility
+        .supported_os
+        .insert(SupportedOS::Windows10);
+    manifest.compatibility.max_version_tested = vec![AssemblyVersion::new(6, 3, 9600, Some(0))];
+    let mut config = LintConfig::new();
+    config.set_all("deny".parse().unwrap());
+    config
+        .set(DPI_MISMATCH.code, Level::Allow)
+        .unwrap()
+        .set(COMMON_CONTROLS_PUBLIC_KEY_TOKEN.code, Level::Warn)
+        .unwrap();
+    assert!(config.set("no-such-lint", Level::Deny).is_err());
+    assert_eq!(
+        summary(manifest.lint(&config)),
+        vec![
+            (
+                Severity::Warning,
+                COMMON_CONTROLS_PUBLIC_KEY_TOKEN.code,
+                ".dependencies.0.dependent_assemblies.0.identity.public_key_token".to_string()
+            ),
+            (
+                Severity::Error,
+                DUPLICATE_DEPENDENCY.code,
+                ".dependencies.1.dependent_assemblies.0.identity.name".to_string()
+            ),
+            (
+                Severity::Error,
+                MAX_VERSION_BELOW_SUPPORTED_OS.code,
+                ".compatibility.max_version_tested".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_schema() {
+    use schema::Schema;
+
+    let schema = Schema::bundled();
+    assert_eq!(
+        schema
+            .validate(include_str!("tests/file.xml").as_bytes())
+            .unwrap(),
+        vec![]
+    );
+
+    let violations = schema
+        .validate(include_str!("tests/schema_invalid.xml").as_bytes())
+        .unwrap()
+        .iter()
+        .map(|violation| violation.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        violations,
+        vec![
+            "5:3 <dependency> unexpected value of optional - maybe is not one of yes, no",
+            "7:7 <bindingRedirect> element is not expected here",
+            "8:7 <assemblyIdentity> unexpected value of publicKeyToken - xyz is not bin.hex",
+            "11:3 <file> required attribute name is missing",
+            "11:3 <file> unexpected value of hashalg - SHA256 is not one of SHA1, SHA, MD5, MD4, MD2",
+            "12:5 <typelib> unexpected value of flags - shiny is not one of control, hidden, restricted, hasdiskimage",
+            "13:5 <comInterfaceProxyStub> unexpected value of numMethods - -1 is not unsigned integer",
+            "15:3 <unknownElement> element is not allowed in assembly",
+        ]
+    );
+}
+
+#[test]
+fn test_extension() {
+    use element::*;
+    use manifest::*;
+    use std::borrow::Cow;
+
+    #[derive(Debug)]
+    struct Marker(&'static str);
+
+    impl SerializableElement for Marker {
+        fn serialize(
+            &self,
+            writer: &mut XmlWriter<'_>,
+            _path: Path<'_>,
+        ) -> error::SerializeResult<()> {
+            let mut namespace = xml::namespace::Namespace::empty();
+            namespace.put("ext", "urn:example");
+            writer.write(XmlEvent::StartElement {
+                name: xml::name::Name::prefixed("marker", "ext"),
+                attributes: Cow::Borrowed(&[xml::attribute::Attribute::new(
+                    xml::name::Name::local("value"),
+                    self.0,
+                )]),
+                namespace: Cow::Owned(namespace),
+            })?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+            Ok(())
+        }
+    }
+
+    let mut manifest = AssemblyManifest::default();
+    manifest.files.push(File::new("example.dll"));
+    let marker: Box<dyn SerializableElement + Send + Sync> = Box::new(Marker("1"));
+    manifest.extensions = vec![
+        marker.into(),
+        XmlFragment::new(
+            "<ext:note lang=\"en\">hello</ext:note><!-- end -->",
+            &[("ext", "urn:example")],
+        )
+        .unwrap()
+        .into(),
+        Extension::element(Marker("2")),
+    ];
+    assert!(XmlFragment::new("<note />", &[("ext", "urn:example")]).is_err());
+    assert!(XmlFragment::new("<ext:note>", &[("ext", "urn:example")]).is_err());
+    assert_eq!(manifest.clone(), manifest);
+
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let serialized = manifest.serialize_to_string_with_config(config).unwrap();
+    assert_eq!(serialized, include_str!("tests/extension.xml"));
+
+    let parsed = serialized.parse::<AssemblyManifest>().unwrap();
+    assert_eq!(parsed.unknown.nodes.len(), 4);
+
+    #[derive(Debug)]
+    struct Unbalanced;
+
+    impl SerializableElement for Unbalanced {
+        fn serialize(
+            &self,
+            writer: &mut XmlWriter<'_>,
+            _path: Path<'_>,
+        ) -> error::SerializeResult<()> {
+            writer.write(XmlEvent::EndElement { name: None })?;
+            Ok(())
+        }
+    }
+
+    manifest.extensions = vec![
+        XmlFragment::new(
+            "<assemblyIdentity name=\"Duplicated\" />",
+            &[("", "urn:schemas-microsoft-com:asm.v1")],
+        )
+        .unwrap()
+        .into(),
+        Extension::element(Unbalanced),
+    ];
+    let problems = manifest
+        .validate()
+        .into_iter()
+        .map(|problem| (problem.severity, problem.code, problem.path))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        problems,
+        vec![
+            (
+                error::Severity::Warning,
+                "extension-modeled-element",
+                ".extensions.0.0".to_string()
+            ),
+            (
+                error::Severity::Error,
+                "extension-write-failed",
+                ".extensions.1".to_string()
+            ),
+        ]
+    );
+    let mut output = Vec::new();
+    assert!(manifest.serialize(&mut output).is_err());
+    assert!(output.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use manifest::*;
+
+    let mut manifest = AssemblyManifest {
+        assembly_identity: Some(AssemblyIdentity {
+            version: Some(AssemblyVersion::new(1, 2, 3, Some(4))),
+            process_architecture: Some(ProcessArchitecture::X86_64),
+            ..AssemblyIdentity::new("Example.Application")
+        }),
+        ..Default::default()
+    };
+    manifest.compatibility.supported_os.extend(vec![
+        SupportedOS::Windows10,
+        SupportedOS::Windows8_1,
+        SupportedOS::Custom(Guid::from_u128(0x01234567_89ab_cdef_0123_456789abcdef)),
+    ]);
+    manifest.dependencies.push(
+        AssemblyIdentity {
+            version: Some(AssemblyVersion::new(6, 0, 0, Some(0))),
+            public_key_token: Some("6595b64144ccf1df".parse().unwrap()),
+            process_architecture: Some(ProcessArchitecture::Any),
+            ..AssemblyIdentity::new("Microsoft.Windows.Common-Controls")
+        }
+        .into(),
+    );
+
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(json["assembly_identity"]["version"], "1.2.3.4");
+    assert_eq!(json["assembly_identity"]["process_architecture"], "amd64");
+    assert_eq!(
+        json["compatibility"]["supported_os"],
+        serde_json::json!([
+            "Windows10",
+            "Windows8.1",
+            "{01234567-89ab-cdef-0123-456789abcdef}"
+        ])
+    );
+    assert_eq!(
+        json["dependencies"][0]["dependent_assemblies"][0]["identity"]["public_key_token"],
+        "6595B64144CCF1DF"
+    );
+    assert_eq!(
+        serde_json::from_value::<AssemblyManifest>(json).unwrap(),
+        manifest
+    );
+
+    let parsed = serde_json::from_str::<AssemblyManifest>(
+        r#"{
+            "compatibility": { "supported_os": ["win11", "Windows 8.1"], "max_version_tested": ["10.0.22000.0"] },
+            "windows_settings": { "dpi_awareness": ["permonitorv2"] }
+        }"#,
+    )
+    .unwrap();
+    manifest = AssemblyManifest::default();
+    manifest
+        .compatibility
+        .supported_os
+        .extend(vec![SupportedOS::Windows10, SupportedOS::Windows8_1]);
+    manifest.compatibility.max_version_tested = vec![windows_version::WINDOWS_11_21H2];
+    manifest.windows_settings.dpi_awareness = vec![DpiAwareness::PerMonitorV2];
+    assert_eq!(parsed, manifest);
+    assert!(serde_json::from_str::<AssemblyVersion>(r#""1.2""#).is_err());
+}
+
+#[cfg(feature = "cargo-metadata")]
+#[test]
+fn test_cargo_metadata() {
+    use metadata::*;
+
+    let package = PackageInfo {
+        name: "example".to_string(),
+        version: "1.2.3-beta.4".to_string(),
+    };
+    let cargo_toml = r#"
+        [package]
+        name = "example"
+
+        [package.metadata.sxs-manifest]
+        compatibility.supported_os = ["Windows10"]
+        windows_settings.dpi_awareness = ["permonitorv2"]
+        trust_info.requested_execution_level.level = "asInvoker"
+
+        [package.metadata.sxs-manifest.bin.installer]
+        assembly_identity.name = "Example.Installer"
+        trust_info.requested_execution_level.level = "requireAdministrator"
+    "#;
+
+    let mut config = EmitterConfig::new();
+    config.indent_string = "".into();
+    config.line_separator = "".into();
+    let manifest = parse(cargo_toml, Some("installer"), &package).unwrap();
+    assert_eq!(
+        manifest.serialize_to_string_with_config(config).unwrap(),
+        include_str!("tests/cargo_metadata.xml")
+    );
+
+    let manifest = parse(cargo_toml, None, &package).unwrap();
+    let identity = manifest.assembly_identity.unwrap();
+    assert_eq!(identity.name, "example");
+    assert_eq!(
+        identity.version,
+        Some(manifest::AssemblyVersion::new(1, 2, 3, Some(4)))
+    );
+    let release = PackageInfo {
+        version: "1.2.3".to_string(),
+        ..package.clone()
+    };
+    assert_eq!(
+        parse("[package]", None, &release)
+            .unwrap()
+            .assembly_identity
+            .unwrap()
+            .version,
+        Some(manifest::AssemblyVersion::new(1, 2, 3, Some(65535)))
+    );
+    assert_eq!(
+        parse("[package]", None, &package).unwrap().compatibility,
+        Default::default()
+    );
+
+    let key_of = |cargo_toml: &str| match parse(cargo_toml, Some("installer"), &package) {
+        Err(MetadataError::Invalid { key, .. }) => key,
+        other => panic!("unexpected result {:?}", other),
+    };
+    assert_eq!(
+        key_of("[package.metadata.sxs-manifest]\ncompatibility.supported_os = [\"Windows10\", \"Windows99\"]"),
+        "package.metadata.sxs-manifest.compatibility.supported_os[1]"
+    );
+    assert_eq!(
+        key_of(
+            "[package.metadata.sxs-manifest.bin.installer]\nassembly_identity.version = \"1.x\""
+        ),
+        "package.metadata.sxs-manifest.bin.installer.assembly_identity.version"
+    );
+    assert_eq!(
+        key_of("[package.metadata.sxs-manifest]\nwindows_settings.dpi_awarenes = []"),
+        "package.metadata.sxs-manifest.windows_settings.dpi_awarenes"
+    );
+    assert_eq!(
+        key_of("[package.metadata]\nsxs-manifest = 1"),
+        "package.metadata.sxs-manifest"
+    );
+}
+
+#[test]
+fn test_embed() {
+    use embed::*;
+
+    assert_eq!(
+        Method::for_target("x86_64-pc-windows-msvc"),
+        Method::MsvcLinker
+    );
+    assert_eq!(
+        Method::for_target("aarch64-uwp-windows-msvc"),
+        Method::MsvcLinker
+    );
+    assert_eq!(Method::for_target("i686-pc-windows-gnu"), Method::Windres);
+    assert_eq!(
+        Method::for_target("aarch64-pc-windows-gnullvm"),
+        Method::Windres
+    );
+    assert_eq!(Method::for_target("x86_64-unknown-linux-gnu"), Method::None);
+    assert_eq!(Method::for_target("x86_64-apple-darwin"), Method::None);
+
+    let out_dir = std::env::temp_dir().join(format!("sxs-manifest-embed-{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let manifest = AssemblyManifest::default();
+
+    let lines = Embed::new("x86_64-unknown-linux-gnu", &out_dir)
+        .write(&manifest)
+        .unwrap();
+    assert!(lines.is_empty());
+    assert!(!out_dir.join("manifest.xml").exists());
+
+    let manifest_path = out_dir.join("manifest.xml");
+    let lines = Embed::new("x86_64-pc-windows-msvc", &out_dir)
+        .write(&manifest)
+        .unwrap();
+    assert_eq!(
+        lines,
+        vec![
+            "cargo:rustc-link-arg=/MANIFEST:EMBED".to_string(),
+            format!(
+                "cargo:rustc-link-arg=/MANIFESTINPUT:{}",
+                manifest_path.display()
+            ),
+        ]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&manifest_path).unwrap(),
+        manifest.serialize_to_string().unwrap()
+    );
+
+    let mut installer = manifest.clone();
+    installer.trust_info.requested_execution_level = Some(manifest::RequestedExecutionLevel {
+        level: manifest::ExecutionLevel::RequireAdministrator,
+        ui_access: None,
+    });
+    let lines = Embed::new("x86_64-pc-windows-msvc", &out_dir)
+        .bin("installer")
+        .write(&installer)
+        .unwrap();
+    assert_eq!(
+        lines[1..],
+        [
+            format!(
+                "cargo:rustc-link-arg-bin=installer=/MANIFESTINPUT:{}",
+                out_dir.join("installer.manifest.xml").display()
+            ),
+            "cargo:rustc-link-arg-bin=installer=/MANIFESTUAC:NO".to_string(),
+        ]
+    );
+
+    let missing = Embed::new("x86_64-pc-windows-gnu", &out_dir)
+        .windres(out_dir.join("missing-windres"))
+        .write(&manifest);
+    assert!(matches!(missing, Err(EmbedError::Windres { .. })));
+    assert_eq!(
+        std::fs::read_to_string(out_dir.join("manifest.rc")).unwrap(),
+        resource::ResourceScript::new(&manifest_path).to_string()
+    );
+
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+#[test]
+fn test_resource_script() {
+    use resource::*;
+
+    assert_eq!(ManifestKind::Executable.resource_id(), 1);
+    assert_eq!(ManifestKind::IsolationAwareDll.resource_id(), 2);
+    assert_eq!(ManifestKind::Other.resource_id(), 3);
+    assert_eq!(LanguageId::new(0x09, 0x01), LanguageId::EN_US);
+
+    let mut script = ResourceScript::new(r"C:\build\out\app.manifest");
+    assert_eq!(script.to_string(), include_str!("tests/manifest.rc"));
+
+    script.kind(ManifestKind::Other).language(LanguageId::EN_US);
+    assert_eq!(
+        script.to_string(),
+        "LANGUAGE 0x9, 0x1\n3 24 \"C:\\\\build\\\\out\\\\app.manifest\"\n"
+    );
+
+    let script = ResourceScript::new("빌드/\"app\".manifest");
+    assert_eq!(
+        script.to_string(),
+        "#pragma code_page(65001)\nLANGUAGE 0x0, 0x0\n1 24 \"빌드/\"\"app\"\".manifest\"\n"
+    );
+
+    let out_dir = std::env::temp_dir().join(format!("sxs-manifest-rc-{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let lines = embed::Embed::new("x86_64-pc-windows-msvc", &out_dir)
+        .kind(ManifestKind::IsolationAwareDll)
+        .write(&AssemblyManifest::default())
+        .unwrap();
+    assert_eq!(lines[0], "cargo:rustc-link-arg=/MANIFEST:EMBED,ID=2");
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+#[test]
+fn test_res_file() {
+    use resource::*;
+
+    let manifest = AssemblyManifest::default();
+    let mut res = ResFile::default();
+    res.insert(Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::EN_US).unwrap());
+    let bytes = res.write(Vec::new()).unwrap();
+
+    let data = manifest.serialize(Vec::new()).unwrap();
+    #[rustfmt::skip]
+    let header: &[u8] = &[
+        // null entry
+        0, 0, 0, 0, 32, 0, 0, 0, 0xff, 0xff, 0, 0, 0xff, 0xff, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        // data size, header size, RT_MANIFEST, 1
+        data.len() as u8, 0, 0, 0, 32, 0, 0, 0, 0xff, 0xff, 24, 0, 0xff, 0xff, 1, 0,
+        // data version, memory flags, en-US, version, characteristics
+        0, 0, 0, 0, 0x30, 0, 0x09, 0x04, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    assert!(data.len() < 256);
+    assert_eq!(&bytes[..64], header);
+    assert_eq!(&bytes[64..64 + data.len()], data.as_slice());
+    assert_eq!(bytes.len() % 4, 0);
+    assert_eq!(bytes.len(), 64 + data.len() + (4 - data.len() % 4) % 4);
+
+    // merge into existing resources, replacing manifest of same ID and language
+    let mut existing = ResFile::default();
+    existing.resources.push(Resource::new(
+        ResourceName::Name("CONFIG".to_string()),
+        ResourceName::Name("DEFAULT".to_string()),
+        LanguageId::NEUTRAL,
+        b"abc".to_vec(),
+    ));
+    existing.resources.push(Resource::new(
+        ResourceName::Id(RT_MANIFEST),
+        ResourceName::Id(1),
+        LanguageId::EN_US,
+        b"<old />".to_vec(),
+    ));
+    let existing = existing.write(Vec::new()).unwrap();
+    let mut merged = ResFile::read(existing.as_slice()).unwrap();
+    assert_eq!(merged.resources.len(), 2);
+    assert_eq!(
+        merged.resources[0].r#type,
+        ResourceName::Name("CONFIG".to_string())
+    );
+    assert_eq!(merged.resources[0].data, b"abc");
+    merged.insert(
+        Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::EN_US).unwrap(),
+    );
+    merged.insert(Resource::manifest(&manifest, ManifestKind::Other, LanguageId::EN_US).unwrap());
+    let merged = ResFile::read(merged.write(Vec::new()).unwrap().as_slice()).unwrap();
+    assert_eq!(merged.resources.len(), 3);
+    assert_eq!(merged.resources[1], res.resources[0]);
+    assert_eq!(merged.resources[2].name, ResourceName::Id(3));
+
+    // manifest replaces ones of other languages, such as rc.exe output at en-US
+    let mut merged = merged;
+    merged.resources.push(Resource::new(
+        ResourceName::Id(RT_MANIFEST),
+        ResourceName::Id(1),
+        LanguageId::new(0x07, 0x01),
+        b"<old />".to_vec(),
+    ));
+    merged.insert(
+        Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::NEUTRAL).unwrap(),
+    );
+    let manifests = merged
+        .resources
+        .iter()
+        .filter(|resource| resource.name == ResourceName::Id(1))
+        .collect::<Vec<_>>();
+    assert_eq!(manifests.len(), 1);
+    assert_eq!(manifests[0].language, LanguageId::NEUTRAL);
+    assert_eq!(merged.resources.len(), 3);
+
+    assert!(matches!(
+        ResFile::read(&bytes[..40]),
+        Err(ResError::Invalid { offset: 32, .. })
+    ));
+}
+
+#[test]
+fn test_coff() {
+    use resource::*;
+
+    let u16_at =
+        |bytes: &[u8], offset: usize| u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+    let u32_at = |bytes: &[u8], offset: usize| {
+        u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+
+    let manifest = AssemblyManifest::default();
+    let manifest_bytes = manifest.serialize(Vec::new()).unwrap();
+    let mut res = ResFile::default();
+    res.insert(
+        Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::NEUTRAL).unwrap(),
+    );
+    res.insert(Resource::new(
+        ResourceName::Name("CONFIG".to_string()),
+        ResourceName::Id(1),
+        LanguageId::EN_US,
+        b"abc".to_vec(),
+    ));
+
+    for (machine, value, relocation) in [
+        (Machine::X86, 0x14c, 7),
+        (Machine::X64, 0x8664, 3),
+        (Machine::Arm64, 0xaa64, 2),
+    ] {
+        let bytes = res.write_coff(machine, Vec::new()).unwrap();
+        assert_eq!(u16_at(&bytes, 0), value);
+        assert_eq!(u16_at(&bytes, 2), 2);
+        assert_eq!(&bytes[20..28], b".rsrc$01");
+        assert_eq!(&bytes[60..68], b".rsrc$02");
+
+        let directory = &bytes[u32_at(&bytes, 20 + 20) as usize..];
+        let relocations = u32_at(&bytes, 20 + 24) as usize;
+        assert_eq!(u16_at(&bytes, 20 + 32), 2);
+        let data = u32_at(&bytes, 60 + 20) as usize;
+        let symbols = u32_at(&bytes, 8) as usize;
+        assert_eq!(u32_at(&bytes, 12), 7);
+
+        // named type is ordered before RT_MANIFEST
+        assert_eq!((u16_at(directory, 12), u16_at(directory, 14)), (1, 1));
+        assert_ne!(u32_at(directory, 16) & 0x8000_0000, 0);
+        assert_eq!(u32_at(directory, 24), u32::from(RT_MANIFEST));
+
+        // manifest is the second data entry, relocated to its symbol
+        let relocation_offset = relocations + 10;
+        let entry = u32_at(&bytes, relocation_offset) as usize;
+        assert_eq!(u16_at(&bytes, relocation_offset + 8), relocation);
+        assert_eq!(u32_at(directory, entry), 0);
+        assert_eq!(u32_at(directory, entry + 4) as usize, manifest_bytes.len());
+        let symbol = symbols + 18 * u32_at(&bytes, relocation_offset + 4) as usize;
+        assert_eq!(&bytes[symbol..symbol + 8], b"$R000001");
+        assert_eq!(u16_at(&bytes, symbol + 12), 2);
+        let offset = data + u32_at(&bytes, symbol + 8) as usize;
+        assert_eq!(
+            &bytes[offset..offset + manifest_bytes.len()],
+            manifest_bytes.as_slice()
+        );
+    }
+
+    assert_eq!(
+        Machine::from_architecture(manifest::ProcessArchitecture::Arm64),
+        Some(Machine::Arm64)
+    );
+    assert_eq!(
+        Machine::from_architecture(manifest::ProcessArchitecture::Any),
+        None
+    );
+}
+
+#[test]
+fn test_split_elements_round_trip() {
+    use manifest::*;
+
+    let manifest: AssemblyManifest = include_str!("tests/split_elements.xml").parse().unwrap();
+    assert_eq!(manifest.windows_settings.dpi_aware, Some(DpiAware::True));
+    assert_eq!(manifest.windows_settings.long_path_aware, Some(true));
+    assert!(manifest
+        .compatibility
+        .supported_os
+        .contains(&SupportedOS::Windows10));
+    assert!(manifest
+        .compatibility
+        .supported_os
+        .contains(&SupportedOS::Windows8_1));
+    assert_eq!(
+        manifest.trust_info.requested_execution_level,
+        Some(RequestedExecutionLevel::new(ExecutionLevel::AsInvoker))
+    );
+    assert_eq!(manifest.windows_settings.unknown.nodes.len(), 1);
+
+    let serialized = manifest.serialize_to_string().unwrap();
+    let parsed: AssemblyManifest = serialized.parse().unwrap();
+    assert_eq!(parsed, manifest);
+}
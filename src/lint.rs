@@ -0,0 +1,306 @@
+//! Lints of manifest best practices
+//!
+//! Unlike [`AssemblyManifest::validate`](../struct.AssemblyManifest.html#method.validate),
+//! lints report manifests which can be written but likely don't behave as intended.
+//! Each lint has a [`Level`] which can be overridden with [`LintConfig`], similar to clippy.
+use crate::{
+    debug::Path,
+    manifest::{
+        AssemblyManifest, DpiAware, DpiAwareness, PublicKeyToken, SupportedOS, WindowsSettings,
+    },
+    serialize::SerializableValue,
+    validate::{Problem, Problems, Severity},
+};
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Level of lint
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Level {
+    /// Lint is not reported
+    Allow,
+    /// Lint is reported as [`Severity::Warning`](../error/enum.Severity.html)
+    Warn,
+    /// Lint is reported as [`Severity::Error`](../error/enum.Severity.html)
+    Deny,
+}
+
+/// Error of parsing [`Level`]
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("unknown lint level - {0}")]
+pub struct LevelParseError(String);
+
+/// Accepts `allow`, `warn` and `deny`, case-insensitive
+impl FromStr for Level {
+    type Err = LevelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "allow" => Ok(Level::Allow),
+            "warn" => Ok(Level::Warn),
+            "deny" => Ok(Level::Deny),
+            _ => Err(LevelParseError(s.to_string())),
+        }
+    }
+}
+
+/// Lint of the catalogue
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Lint {
+    /// Stable identifier, which is also [`Problem::code`](../error/struct.Problem.html#structfield.code)
+    pub code: &'static str,
+    #[allow(missing_docs)]
+    pub default_level: Level,
+    #[allow(missing_docs)]
+    pub description: &'static str,
+}
+
+/// DPI settings without Windows 10 `supportedOS`
+pub const DPI_WITHOUT_WINDOWS10: Lint = Lint {
+    code: "dpi-without-windows10",
+    default_level: Level::Warn,
+    description: "DPI awareness is set, but Windows 10 is not in supported_os",
+};
+/// `dpiAware` and `dpiAwareness` disagree
+pub const DPI_MISMATCH: Lint = Lint {
+    code: "dpi-mismatch",
+    default_level: Level::Warn,
+    description: "dpi_aware and dpi_awareness request different awareness",
+};
+/// Common Controls dependency with unexpected publicKeyToken
+pub const COMMON_CONTROLS_PUBLIC_KEY_TOKEN: Lint = Lint {
+    code: "common-controls-public-key-token",
+    default_level: Level::Deny,
+    description: "dependency on Common Controls has wrong public_key_token",
+};
+/// `maxversiontested` is older than the newest `supportedOS`
+pub const MAX_VERSION_BELOW_SUPPORTED_OS: Lint = Lint {
+    code: "max-version-below-supported-os",
+    default_level: Level::Warn,
+    description: "max_version_tested is older than the newest supported_os",
+};
+/// Same assembly is depended on twice
+pub const DUPLICATE_DEPENDENCY: Lint = Lint {
+    code: "duplicate-dependency",
+    default_level: Level::Warn,
+    description: "dependent assembly with the same name is listed twice",
+};
+
+/// All lints
+pub const LINTS: &[Lint] = &[
+    DPI_WITHOUT_WINDOWS10,
+    DPI_MISMATCH,
+    COMMON_CONTROLS_PUBLIC_KEY_TOKEN,
+    MAX_VERSION_BELOW_SUPPORTED_OS,
+    DUPLICATE_DEPENDENCY,
+];
+
+/// Find lint by code
+pub fn find(code: &str) -> Option<&'static Lint> {
+    LINTS.iter().find(|lint| lint.code == code)
+}
+
+/// Error of configuring lint which is not in [`LINTS`]
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("unknown lint - {0}")]
+pub struct UnknownLintError(String);
+
+/// Levels of lints, overriding their default levels
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct LintConfig {
+    all: Option<Level>,
+    levels: BTreeMap<&'static str, Level>,
+}
+
+impl LintConfig {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        LintConfig::default()
+    }
+
+    /// Set level of lint
+    pub fn set(&mut self, code: &str, level: Level) -> Result<&mut Self, UnknownLintError> {
+        let lint = find(code).ok_or_else(|| UnknownLintError(code.to_string()))?;
+        self.levels.insert(lint.code, level);
+        Ok(self)
+    }
+
+    /// Set level of all lints which are not set individually, such as `deny` for CI
+    pub fn set_all(&mut self, level: Level) -> &mut Self {
+        self.all = Some(level);
+        self
+    }
+
+    /// Effective level of lint
+    pub fn level(&self, lint: &Lint) -> Level {
+        self.levels
+            .get(lint.code)
+            .copied()
+            .or(self.all)
+            .unwrap_or(lint.default_level)
+    }
+}
+
+/// Lint of manifest
+impl AssemblyManifest {
+    /// Run all lints, and return the ones which aren't allowed by `config`.
+    ///
+    /// Denied lints have [`Severity::Error`](error/enum.Severity.html).
+    pub fn lint(&self, config: &LintConfig) -> Vec<Problem> {
+        let mut problems = Problems::default();
+        lint_dpi(self, &mut problems);
+        lint_dependencies(self, &mut problems);
+        lint_max_version_tested(self, &mut problems);
+
+        problems
+            .into_vec()
+            .into_iter()
+            .filter_map(|mut problem| {
+                let lint = find(problem.code).expect("lint is in catalogue");
+                problem.severity = match config.level(lint) {
+                    Level::Allow => return None,
+                    Level::Warn => Severity::Warning,
+                    Level::Deny => Severity::Error,
+                };
+                Some(problem)
+            })
+            .collect()
+    }
+}
+
+fn lint_dpi(manifest: &AssemblyManifest, problems: &mut Problems) {
+    let settings = &manifest.windows_settings;
+    let path = Path::new("windows_settings".into());
+    let (dpi_path, dpi_set) = if !settings.dpi_awareness.is_empty() {
+        (path.appended("dpi_awareness".into()), true)
+    } else {
+        (
+            path.appended("dpi_aware".into()),
+            settings.dpi_aware.is_some(),
+        )
+    };
+    if dpi_set
+        && !manifest
+            .compatibility
+            .supported_os
+            .contains(&SupportedOS::Windows10)
+    {
+        problems.warning(
+            &dpi_path,
+            DPI_WITHOUT_WINDOWS10.code,
+            DPI_WITHOUT_WINDOWS10.description,
+            "add SupportedOS::Windows10 to supported_os",
+        );
+    }
+
+    if let Some(message) = dpi_mismatch(settings) {
+        problems.warning(
+            &path.appended("dpi_aware".into()),
+            DPI_MISMATCH.code,
+            message,
+            "use `true` or `true/pm` for dpi_aware as fallback of dpi_awareness",
+        );
+    }
+}
+
+/// Legacy setting is the fallback of `dpiAwareness`, so both must be aware or unaware,
+/// and per-monitor legacy setting must not downgrade to system awareness.
+fn dpi_mismatch(settings: &WindowsSettings) -> Option<String> {
+    let dpi_aware = settings.dpi_aware?;
+    let legacy_rank = match dpi_aware {
+        DpiAware::False => 0,
+        DpiAware::True => 1,
+        DpiAware::TruePerMonitor | DpiAware::PerMonitor => 2,
+    };
+    let rank = settings
+        .dpi_awareness
+        .iter()
+        .map(|awareness| match awareness {
+            DpiAwareness::Unaware => 0,
+            DpiAwareness::System => 1,
+            DpiAwareness::PerMonitor | DpiAwareness::PerMonitorV2 => 2,
+        })
+        .max()?;
+    if (legacy_rank == 0) != (rank == 0) || (legacy_rank == 2 && rank < 2) {
+        Some(format!(
+            "dpi_aware is {:?}, but dpi_awareness is {:?}",
+            dpi_aware, settings.dpi_awareness
+        ))
+    } else {
+        None
+    }
+}
+
+fn lint_dependencies(manifest: &AssemblyManifest, problems: &mut Problems) {
+    const COMMON_CONTROLS_NAME: &str = "Microsoft.Windows.Common-Controls";
+    const COMMON_CONTROLS_TOKEN: PublicKeyToken =
+        PublicKeyToken([0x65, 0x95, 0xb6, 0x41, 0x44, 0xcc, 0xf1, 0xdf]);
+
+    let path = Path::new("dependencies".into());
+    let assemblies = manifest
+        .dependencies
+        .iter()
+        .enumerate()
+        .flat_map(|(dependency_idx, dependency)| {
+            dependency
+                .dependent_assemblies
+                .iter()
+                .enumerate()
+                .map(move |(idx, assembly)| (dependency_idx, idx, assembly))
+        })
+        .collect::<Vec<_>>();
+    for (position, (dependency_idx, idx, assembly)) in assemblies.iter().enumerate() {
+        let identity = &assembly.identity;
+        let dependency_path = path.appended((*dependency_idx).into());
+        let assemblies_path = dependency_path.appended("dependent_assemblies".into());
+        let assembly_path = assemblies_path.appended((*idx).into());
+        let identity_path = assembly_path.appended("identity".into());
+        if identity.name.eq_ignore_ascii_case(COMMON_CONTROLS_NAME)
+            && identity.public_key_token != Some(COMMON_CONTROLS_TOKEN)
+        {
+            problems.warning(
+                &identity_path.appended("public_key_token".into()),
+                COMMON_CONTROLS_PUBLIC_KEY_TOKEN.code,
+                format!(
+                    "public_key_token of Common Controls must be {}",
+                    COMMON_CONTROLS_TOKEN.serialize()
+                ),
+                "set public_key_token to `6595b64144ccf1df`",
+            );
+        }
+        if assemblies[..position]
+            .iter()
+            .any(|(_, _, other)| other.identity.name.eq_ignore_ascii_case(&identity.name))
+        {
+            problems.warning(
+                &identity_path.appended("name".into()),
+                DUPLICATE_DEPENDENCY.code,
+                format!("{} is already listed", identity.name),
+                "remove duplicated dependent assembly",
+            );
+        }
+    }
+}
+
+fn lint_max_version_tested(manifest: &AssemblyManifest, problems: &mut Problems) {
+    let compatibility = &manifest.compatibility;
+    let newest_os = compatibility
+        .supported_os
+        .iter()
+        .filter_map(|os| os.version().map(|version| (os, version)))
+        .max_by_key(|(_, version)| *version);
+    let max_version_tested = compatibility.max_version_tested.iter().max();
+    if let (Some((os, os_version)), Some(max_version_tested)) = (newest_os, max_version_tested) {
+        if *max_version_tested < os_version {
+            let path = Path::new("compatibility".into());
+            problems.warning(
+                &path.appended("max_version_tested".into()),
+                MAX_VERSION_BELOW_SUPPORTED_OS.code,
+                format!(
+                    "max_version_tested {} is older than {:?} ({})",
+                    max_version_tested, os, os_version
+                ),
+                "set max_version_tested to the latest version tested, such as `windows_version::WINDOWS_10_22H2`",
+            );
+        }
+    }
+}
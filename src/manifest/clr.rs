@@ -0,0 +1,363 @@
+use super::{
+    common::Guid,
+    file::{validate_progids, ThreadingModel},
+    unknown::UnknownContent,
+};
+use crate::xml_alias::{XmlAttribute, XmlName, XmlNamespace};
+use crate::{
+    consts::NS_MS_ASM_V1,
+    debug::Path,
+    deserialize::{DeserializableElement, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
+
+/// Runtime version is written as `v4.0.30319`
+fn validate_runtime_version(
+    runtime_version: &Option<String>,
+    path: &Path<'_>,
+    problems: &mut Problems,
+) {
+    if let Some(runtime_version) = runtime_version {
+        let valid = runtime_version
+            .strip_prefix('v')
+            .map(|version| {
+                version
+                    .split('.')
+                    .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+            })
+            .unwrap_or(false);
+        if !valid {
+            problems.error(
+                &path.appended("runtime_version".into()),
+                "runtime-version",
+                format!("unexpected runtime version - {}", runtime_version),
+                "use version of CLR such as `v4.0.30319`",
+            );
+        }
+    }
+}
+
+fn validate_name(name: &str, path: &Path<'_>, problems: &mut Problems) {
+    if name.is_empty() {
+        problems.error(
+            &path.appended("name".into()),
+            "empty-name",
+            "name of class is required",
+            "set full name of managed class, such as `Namespace.Class`",
+        );
+    }
+}
+
+/// Managed class exposed to COM (`clrClass`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClrClass {
+    /// Full name of the managed class
+    pub name: String,
+    #[allow(missing_docs)]
+    pub clsid: Guid,
+    /// Version-dependent ProgID
+    pub progid: Option<String>,
+    /// Type library which describes the class
+    pub tlbid: Option<Guid>,
+    #[allow(missing_docs)]
+    pub description: Option<String>,
+    /// Version of CLR, such as `v4.0.30319`
+    pub runtime_version: Option<String>,
+    #[allow(missing_docs)]
+    pub threading_model: Option<ThreadingModel>,
+    /// Additional ProgIDs, such as version-independent one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progids: Vec<String>,
+    /// Content of `clrClass` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl ClrClass {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "clrClass",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ELEMENT_PROGID_NAME: XmlName<'static> = XmlName {
+        local_name: "progid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NAME_NAME: XmlName<'static> = XmlName {
+        local_name: "name",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_CLSID_NAME: XmlName<'static> = XmlName {
+        local_name: "clsid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_PROGID_NAME: XmlName<'static> = XmlName {
+        local_name: "progid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_TLBID_NAME: XmlName<'static> = XmlName {
+        local_name: "tlbid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_DESCRIPTION_NAME: XmlName<'static> = XmlName {
+        local_name: "description",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_RUNTIME_VERSION_NAME: XmlName<'static> = XmlName {
+        local_name: "runtimeVersion",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_THREADING_MODEL_NAME: XmlName<'static> = XmlName {
+        local_name: "threadingModel",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new<S: AsRef<str>>(name: S, clsid: Guid) -> Self {
+        ClrClass {
+            name: name.as_ref().to_string(),
+            clsid,
+            progid: None,
+            tlbid: None,
+            description: None,
+            runtime_version: None,
+            threading_model: None,
+            progids: Vec::new(),
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for ClrClass {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        validate_name(&self.name, &path, problems);
+        validate_progids(&self.progid, &self.progids, &path, problems);
+        validate_runtime_version(&self.runtime_version, &path, problems);
+    }
+}
+
+impl SerializableElement for ClrClass {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let mut values = vec![
+            (
+                ClrClass::ATTRIBUTE_NAME_NAME,
+                Cow::Borrowed(self.name.as_str()),
+            ),
+            (ClrClass::ATTRIBUTE_CLSID_NAME, self.clsid.serialize()),
+        ];
+        if let Some(progid) = &self.progid {
+            values.push((ClrClass::ATTRIBUTE_PROGID_NAME, Cow::Borrowed(progid)));
+        }
+        if let Some(tlbid) = &self.tlbid {
+            values.push((ClrClass::ATTRIBUTE_TLBID_NAME, tlbid.serialize()));
+        }
+        if let Some(description) = &self.description {
+            values.push((
+                ClrClass::ATTRIBUTE_DESCRIPTION_NAME,
+                Cow::Borrowed(description),
+            ));
+        }
+        if let Some(runtime_version) = &self.runtime_version {
+            values.push((
+                ClrClass::ATTRIBUTE_RUNTIME_VERSION_NAME,
+                Cow::Borrowed(runtime_version),
+            ));
+        }
+        if let Some(threading_model) = &self.threading_model {
+            values.push((
+                ClrClass::ATTRIBUTE_THREADING_MODEL_NAME,
+                threading_model.serialize(),
+            ));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: ClrClass::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+
+        let unknown_path = path.appended("unknown".into());
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        for progid in &self.progids {
+            writer.write(XmlEvent::StartElement {
+                name: ClrClass::ELEMENT_PROGID_NAME,
+                attributes: Cow::Borrowed(&[]),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::Characters(progid))?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(ClrClass::ELEMENT_PROGID_NAME.local_name),
+        )?;
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for ClrClass {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let progids_path = path.appended("progids".into());
+        Ok(ClrClass {
+            name: element.required_value("name", &path.appended("name".into()))?,
+            clsid: element.required_value("clsid", &path.appended("clsid".into()))?,
+            progid: element.value("progid", &path.appended("progid".into()))?,
+            tlbid: element.value("tlbid", &path.appended("tlbid".into()))?,
+            description: element.value("description", &path.appended("description".into()))?,
+            runtime_version: element
+                .value("runtimeVersion", &path.appended("runtime_version".into()))?,
+            threading_model: element
+                .value("threadingModel", &path.appended("threading_model".into()))?,
+            progids: element
+                .elements()
+                .filter(|child| child.is(&ClrClass::ELEMENT_PROGID_NAME))
+                .enumerate()
+                .map(|(idx, progid)| progid.text_value(&progids_path.appended(idx.into())))
+                .collect::<ParseResult<_>>()?,
+            unknown: UnknownContent::capture(
+                element,
+                |child| child.is(&ClrClass::ELEMENT_PROGID_NAME),
+                &[
+                    ClrClass::ATTRIBUTE_NAME_NAME.local_name,
+                    ClrClass::ATTRIBUTE_CLSID_NAME.local_name,
+                    ClrClass::ATTRIBUTE_PROGID_NAME.local_name,
+                    ClrClass::ATTRIBUTE_TLBID_NAME.local_name,
+                    ClrClass::ATTRIBUTE_DESCRIPTION_NAME.local_name,
+                    ClrClass::ATTRIBUTE_RUNTIME_VERSION_NAME.local_name,
+                    ClrClass::ATTRIBUTE_THREADING_MODEL_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
+
+/// Managed class which is activated in surrogate process (`clrSurrogate`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClrSurrogate {
+    #[allow(missing_docs)]
+    pub clsid: Guid,
+    /// Full name of the managed class
+    pub name: String,
+    /// Version of CLR, such as `v4.0.30319`
+    pub runtime_version: Option<String>,
+    /// Content of `clrSurrogate` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl ClrSurrogate {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "clrSurrogate",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_CLSID_NAME: XmlName<'static> = XmlName {
+        local_name: "clsid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NAME_NAME: XmlName<'static> = XmlName {
+        local_name: "name",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_RUNTIME_VERSION_NAME: XmlName<'static> = XmlName {
+        local_name: "runtimeVersion",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new<S: AsRef<str>>(clsid: Guid, name: S) -> Self {
+        ClrSurrogate {
+            clsid,
+            name: name.as_ref().to_string(),
+            runtime_version: None,
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for ClrSurrogate {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        validate_name(&self.name, &path, problems);
+        validate_runtime_version(&self.runtime_version, &path, problems);
+    }
+}
+
+impl SerializableElement for ClrSurrogate {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let mut values = vec![
+            (ClrSurrogate::ATTRIBUTE_CLSID_NAME, self.clsid.serialize()),
+            (
+                ClrSurrogate::ATTRIBUTE_NAME_NAME,
+                Cow::Borrowed(self.name.as_str()),
+            ),
+        ];
+        if let Some(runtime_version) = &self.runtime_version {
+            values.push((
+                ClrSurrogate::ATTRIBUTE_RUNTIME_VERSION_NAME,
+                Cow::Borrowed(runtime_version),
+            ));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: ClrSurrogate::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown
+            .serialize_nodes(writer, &path.appended("unknown".into()), None)?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for ClrSurrogate {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        Ok(ClrSurrogate {
+            clsid: element.required_value("clsid", &path.appended("clsid".into()))?,
+            name: element.required_value("name", &path.appended("name".into()))?,
+            runtime_version: element
+                .value("runtimeVersion", &path.appended("runtime_version".into()))?,
+            unknown: UnknownContent::capture(
+                element,
+                |_| false,
+                &[
+                    ClrSurrogate::ATTRIBUTE_CLSID_NAME.local_name,
+                    ClrSurrogate::ATTRIBUTE_NAME_NAME.local_name,
+                    ClrSurrogate::ATTRIBUTE_RUNTIME_VERSION_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
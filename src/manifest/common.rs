@@ -1,18 +1,134 @@
+use super::unknown::UnknownContent;
 use crate::xml_alias::{XmlAttribute, XmlName, XmlNamespace};
 use crate::{
     consts::NS_MS_ASM_V1,
     debug::Path,
-    serialize::{SerializableElement, SerializableValue, SerializeResult},
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
 };
-use std::{borrow::Cow, io::Write};
-use xml::{writer::XmlEvent, EventWriter};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
+
+/// GUID, such as CLSID, IID and type library id
+///
+/// Serialized as lowercase registry format with braces, e.g. `{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}`
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Guid(pub u128);
+
+impl Guid {
+    #[allow(missing_docs)]
+    pub const fn from_u128(value: u128) -> Self {
+        Guid(value)
+    }
+}
+
+impl std::fmt::Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{{:08x}-{:04x}-{:04x}-{:04x}-{:012x}}}",
+            (self.0 >> 96) as u32,
+            (self.0 >> 80) as u16,
+            (self.0 >> 64) as u16,
+            (self.0 >> 48) as u16,
+            self.0 & 0xffff_ffff_ffff
+        )
+    }
+}
+
+/// Error of parsing [`Guid`](struct.Guid.html)
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("invalid GUID - {0}")]
+pub struct GuidParseError(String);
+
+impl std::str::FromStr for Guid {
+    type Err = GuidParseError;
+
+    /// Parse registry format with or without braces, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || GuidParseError(s.to_string());
+        let inner = s
+            .strip_prefix('{')
+            .and_then(|inner| inner.strip_suffix('}'))
+            .unwrap_or(s);
+        let groups = inner.split('-').collect::<Vec<_>>();
+        if groups.len() != 5
+            || groups
+                .iter()
+                .zip(&[8, 4, 4, 4, 12])
+                .any(|(group, len)| group.len() != *len)
+        {
+            return Err(err());
+        }
+
+        let mut value = 0u128;
+        for group in groups {
+            for c in group.chars() {
+                value = (value << 4) | c.to_digit(16).ok_or_else(err)? as u128;
+            }
+        }
+
+        Ok(Guid(value))
+    }
+}
+
+impl SerializableValue for Guid {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+}
+
+impl DeserializableValue for Guid {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
+/// `yes` / `no` attribute value
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub(crate) struct YesNo(pub bool);
+
+impl SerializableValue for YesNo {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(if self.0 { "yes" } else { "no" })
+    }
+}
+
+impl DeserializableValue for YesNo {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "yes" => Some(YesNo(true)),
+            "no" => Some(YesNo(false)),
+            _ => None,
+        }
+    }
+}
+
+/// Format `bin.hex` value
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parse `bin.hex` value
+pub(crate) fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(&value[idx..idx + 2], 16).ok())
+        .collect()
+}
 
 /// Type of assembly.
 ///
 /// Only win32 is available
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
 pub enum AssemblyType {
     #[allow(missing_docs)]
+    #[default]
     Win32,
 }
 
@@ -24,26 +140,119 @@ impl SerializableValue for AssemblyType {
     }
 }
 
+impl DeserializableValue for AssemblyType {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "win32" => Some(AssemblyType::Win32),
+            _ => None,
+        }
+    }
+}
+
 /// Supported process architecture
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum ProcessArchitecture {
     /// x86
     X86,
-    /// x86_64 / ia64 / amd64
+    /// x86_64, written as `amd64`
     X86_64,
+    /// Itanium
+    Ia64,
+    /// 32-bit ARM
+    Arm,
+    /// 64-bit ARM
+    Arm64,
+    /// .NET assembly which runs on any architecture
+    Msil,
+    /// Any architecture, written as `*`. Commonly used with dependencies.
+    Any,
+}
+
+impl ProcessArchitecture {
+    /// Architecture of Rust `target_arch`, such as `x86_64` or `aarch64`
+    pub fn from_target_arch(arch: &str) -> Option<Self> {
+        match arch {
+            "x86" => Some(ProcessArchitecture::X86),
+            "x86_64" => Some(ProcessArchitecture::X86_64),
+            "arm" => Some(ProcessArchitecture::Arm),
+            "aarch64" | "arm64ec" => Some(ProcessArchitecture::Arm64),
+            _ => None,
+        }
+    }
+
+    /// Architecture of Rust target triple, such as `x86_64-pc-windows-msvc`
+    pub fn from_target_triple(triple: &str) -> Option<Self> {
+        let arch = triple.split('-').next()?;
+        match arch {
+            "i386" | "i586" | "i686" => Some(ProcessArchitecture::X86),
+            "x86_64" => Some(ProcessArchitecture::X86_64),
+            // checked before `arm` prefix, since `arm64ec` also starts with it
+            arch if arch.starts_with("aarch64") || arch.starts_with("arm64") => {
+                Some(ProcessArchitecture::Arm64)
+            }
+            arch if arch.starts_with("arm") || arch.starts_with("thumb") => {
+                Some(ProcessArchitecture::Arm)
+            }
+            _ => None,
+        }
+    }
+
+    /// Architecture of current build target, from `CARGO_CFG_TARGET_ARCH`.
+    ///
+    /// Cargo sets this variable only while running build scripts.
+    pub fn from_cargo_env() -> Option<Self> {
+        ProcessArchitecture::from_target_arch(&std::env::var("CARGO_CFG_TARGET_ARCH").ok()?)
+    }
 }
 
 impl SerializableValue for ProcessArchitecture {
     fn serialize(&self) -> Cow<'_, str> {
         Cow::Borrowed(match self {
             ProcessArchitecture::X86 => "x86",
-            ProcessArchitecture::X86_64 => "ia64",
+            ProcessArchitecture::X86_64 => "amd64",
+            ProcessArchitecture::Ia64 => "ia64",
+            ProcessArchitecture::Arm => "arm",
+            ProcessArchitecture::Arm64 => "arm64",
+            ProcessArchitecture::Msil => "msil",
+            ProcessArchitecture::Any => "*",
         })
     }
 }
 
+/// Error of parsing [`ProcessArchitecture`](enum.ProcessArchitecture.html)
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("invalid process architecture - {0}")]
+pub struct ProcessArchitectureParseError(String);
+
+impl std::str::FromStr for ProcessArchitecture {
+    type Err = ProcessArchitectureParseError;
+
+    /// Parse value of `processorArchitecture`, such as `amd64` or `*`, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "x86" => Ok(ProcessArchitecture::X86),
+            "amd64" => Ok(ProcessArchitecture::X86_64),
+            "ia64" => Ok(ProcessArchitecture::Ia64),
+            "arm" => Ok(ProcessArchitecture::Arm),
+            "arm64" => Ok(ProcessArchitecture::Arm64),
+            "msil" => Ok(ProcessArchitecture::Msil),
+            "*" => Ok(ProcessArchitecture::Any),
+            _ => Err(ProcessArchitectureParseError(s.to_string())),
+        }
+    }
+}
+
+impl DeserializableValue for ProcessArchitecture {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
 /// Specific version of assembly
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+///
+/// Ordered by major, minor, build and revision.
+/// Missing revision is written as 0, so `1.2.3` and `1.2.3.0` are equal.
+#[derive(Debug, Copy, Clone)]
 pub struct AssemblyVersion {
     #[allow(missing_docs)]
     pub major: u32,
@@ -67,15 +276,192 @@ impl AssemblyVersion {
     }
 }
 
-impl SerializableValue for AssemblyVersion {
-    fn serialize(&self) -> Cow<'_, str> {
-        Cow::Owned(format!(
+impl PartialEq for AssemblyVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for AssemblyVersion {}
+
+impl PartialOrd for AssemblyVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AssemblyVersion {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl std::hash::Hash for AssemblyVersion {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl AssemblyVersion {
+    /// Maximum value of each component. SxS versions consist of four 16-bit numbers.
+    pub const COMPONENT_MAX: u32 = u16::MAX as u32;
+
+    /// Convert semantic version, such as `1.2.3` or `1.2.3-beta.4`.
+    ///
+    /// Revision is [`COMPONENT_MAX`](#associatedconstant.COMPONENT_MAX) for releases, e.g. `1.2.3.65535`,
+    /// so pre-releases of the same version are ordered below them.
+    /// For pre-releases, it is the trailing number of the last pre-release identifier,
+    /// or 0 if it has no number, e.g. `1.2.3.4` for `1.2.3-beta.4`.
+    /// Pre-releases are ordered only by the number, so `rc.1` is below `beta.2`.
+    /// Pre-release number must be less than `COMPONENT_MAX`.
+    ///
+    /// Build metadata is ignored.
+    pub fn from_semver(version: &str) -> Result<Self, AssemblyVersionParseError> {
+        let err = || AssemblyVersionParseError(version.to_string());
+        let without_metadata = version.split('+').next().unwrap_or(version);
+        let mut parts = without_metadata.splitn(2, '-');
+        let core = parts.next().ok_or_else(err)?;
+        let revision = match parts.next() {
+            Some(pre) => {
+                let last = pre.rsplit('.').next().unwrap_or(pre);
+                let digits = &last[last.trim_end_matches(|c: char| c.is_ascii_digit()).len()..];
+                let number = if digits.is_empty() {
+                    0
+                } else {
+                    digits.parse().map_err(|_| err())?
+                };
+                if number >= AssemblyVersion::COMPONENT_MAX {
+                    return Err(err());
+                }
+                number
+            }
+            None => AssemblyVersion::COMPONENT_MAX,
+        };
+
+        let core = core
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| err()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match core.as_slice() {
+            [major, minor, build] => {
+                let version = AssemblyVersion::new(*major, *minor, *build, Some(revision));
+                if version.is_in_range() {
+                    Ok(version)
+                } else {
+                    Err(err())
+                }
+            }
+            _ => Err(err()),
+        }
+    }
+
+    /// Convert version of current package, from `CARGO_PKG_VERSION`.
+    ///
+    /// Cargo sets this variable while running build scripts.
+    pub fn from_cargo_env() -> Option<Self> {
+        AssemblyVersion::from_semver(&std::env::var("CARGO_PKG_VERSION").ok()?).ok()
+    }
+
+    /// Components compared by `Eq` and `Ord`, with missing revision as 0
+    fn key(&self) -> (u32, u32, u32, u32) {
+        (
+            self.major,
+            self.minor,
+            self.build,
+            self.revision.unwrap_or(0),
+        )
+    }
+
+    fn components(&self) -> [(&'static str, u32); 4] {
+        [
+            ("major", self.major),
+            ("minor", self.minor),
+            ("build", self.build),
+            ("revision", self.revision.unwrap_or(0)),
+        ]
+    }
+
+    fn is_in_range(&self) -> bool {
+        self.components()
+            .iter()
+            .all(|(_, value)| *value <= AssemblyVersion::COMPONENT_MAX)
+    }
+}
+
+/// Check all components are in 16-bit range
+impl Validatable for AssemblyVersion {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        for (name, value) in self.components().iter() {
+            if *value > AssemblyVersion::COMPONENT_MAX {
+                problems.error(
+                    &path.appended((*name).into()),
+                    "version-out-of-range",
+                    format!(
+                        "{} must be at most {}, but {}",
+                        name,
+                        AssemblyVersion::COMPONENT_MAX,
+                        value
+                    ),
+                    "each part of version must fit in 16 bits",
+                );
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for AssemblyVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
             "{}.{}.{}.{}",
             self.major,
             self.minor,
             self.build,
             self.revision.unwrap_or(0)
-        ))
+        )
+    }
+}
+
+/// Error of parsing [`AssemblyVersion`](struct.AssemblyVersion.html)
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("invalid assembly version - {0}")]
+pub struct AssemblyVersionParseError(String);
+
+impl std::str::FromStr for AssemblyVersion {
+    type Err = AssemblyVersionParseError;
+
+    /// Parse `major.minor.build` or `major.minor.build.revision`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || AssemblyVersionParseError(s.to_string());
+        let parts = s
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| err()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let version = match parts.as_slice() {
+            [major, minor, build] => AssemblyVersion::new(*major, *minor, *build, None),
+            [major, minor, build, revision] => {
+                AssemblyVersion::new(*major, *minor, *build, Some(*revision))
+            }
+            _ => return Err(err()),
+        };
+
+        if version.is_in_range() {
+            Ok(version)
+        } else {
+            Err(err())
+        }
+    }
+}
+
+impl SerializableValue for AssemblyVersion {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+}
+
+impl DeserializableValue for AssemblyVersion {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
     }
 }
 
@@ -92,10 +478,39 @@ impl SerializableValue for PublicKeyToken {
     }
 }
 
+/// Error of parsing [`PublicKeyToken`](struct.PublicKeyToken.html)
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("invalid public key token - {0}")]
+pub struct PublicKeyTokenParseError(String);
+
+impl std::str::FromStr for PublicKeyToken {
+    type Err = PublicKeyTokenParseError;
+
+    /// Parse 16 hexadecimal digits, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = from_hex(s)
+            .filter(|bytes| bytes.len() == 8)
+            .ok_or_else(|| PublicKeyTokenParseError(s.to_string()))?;
+
+        let mut token = [0u8; 8];
+        token.copy_from_slice(&bytes);
+
+        Ok(PublicKeyToken(token))
+    }
+}
+
+impl DeserializableValue for PublicKeyToken {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse().ok()
+    }
+}
+
 /// Specific assembly
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssemblyIdentity {
     #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub r#type: AssemblyType,
     #[allow(missing_docs)]
     pub name: String,
@@ -107,10 +522,13 @@ pub struct AssemblyIdentity {
     pub version: Option<AssemblyVersion>,
     #[allow(missing_docs)]
     pub public_key_token: Option<PublicKeyToken>,
+    /// Content of `assemblyIdentity` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
 }
 
 impl AssemblyIdentity {
-    const ELEMENT_NAME: XmlName<'static> = XmlName {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
         local_name: "assemblyIdentity",
         namespace: Some(NS_MS_ASM_V1),
         prefix: None,
@@ -156,16 +574,29 @@ impl AssemblyIdentity {
             process_architecture: None,
             version: None,
             public_key_token: None,
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for AssemblyIdentity {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if self.name.is_empty() {
+            problems.error(
+                &path.appended("name".into()),
+                "empty-name",
+                "name of assembly is required",
+                "set name such as `Company.Product.Component`",
+            );
+        }
+        if let Some(version) = &self.version {
+            version.validate(path.appended("version".into()), problems);
         }
     }
 }
 
 impl SerializableElement for AssemblyIdentity {
-    fn serialize<W: Write>(
-        &self,
-        writer: &mut EventWriter<W>,
-        _path: Path<'_>,
-    ) -> SerializeResult<()> {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
         let mut attributes = Vec::<XmlAttribute>::new();
 
         let type_val = self.r#type.serialize();
@@ -186,11 +617,10 @@ impl SerializableElement for AssemblyIdentity {
             });
         }
 
-        let process_architecture = if let Some(process_architecture) = &self.process_architecture {
-            Some(process_architecture.serialize())
-        } else {
-            None
-        };
+        let process_architecture = self
+            .process_architecture
+            .as_ref()
+            .map(|process_architecture| process_architecture.serialize());
         if let Some(process_architecture) = &process_architecture {
             attributes.push(XmlAttribute {
                 name: AssemblyIdentity::ATTRIBUTE_PROCESS_ARCHITECTURE_NAME,
@@ -198,11 +628,7 @@ impl SerializableElement for AssemblyIdentity {
             });
         }
 
-        let version = if let Some(version) = &self.version {
-            Some(version.serialize())
-        } else {
-            None
-        };
+        let version = self.version.as_ref().map(|version| version.serialize());
         if let Some(version) = &version {
             attributes.push(XmlAttribute {
                 name: AssemblyIdentity::ATTRIBUTE_VERSION_NAME,
@@ -210,11 +636,10 @@ impl SerializableElement for AssemblyIdentity {
             });
         }
 
-        let public_key_token = if let Some(public_key_token) = &self.public_key_token {
-            Some(public_key_token.serialize())
-        } else {
-            None
-        };
+        let public_key_token = self
+            .public_key_token
+            .as_ref()
+            .map(|public_key_token| public_key_token.serialize());
         if let Some(public_key_token) = &public_key_token {
             attributes.push(XmlAttribute {
                 name: AssemblyIdentity::ATTRIBUTE_PUBLIC_KEY_TOKEN_NAME,
@@ -222,13 +647,48 @@ impl SerializableElement for AssemblyIdentity {
             });
         }
 
+        self.unknown.extend_attributes(&mut attributes);
+
         writer.write(XmlEvent::StartElement {
             name: AssemblyIdentity::ELEMENT_NAME,
             attributes: Cow::Borrowed(&attributes),
-            namespace: Cow::Borrowed(&XmlNamespace::empty()),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
         })?;
+        self.unknown
+            .serialize_nodes(writer, &path.appended("unknown".into()), None)?;
         writer.write(XmlEvent::EndElement { name: None })?;
 
         Ok(())
     }
 }
+
+impl DeserializableElement for AssemblyIdentity {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        Ok(AssemblyIdentity {
+            r#type: element
+                .value("type", &path.appended("type".into()))?
+                .unwrap_or(AssemblyType::Win32),
+            name: element.required_value("name", &path.appended("name".into()))?,
+            language: element.value("language", &path.appended("language".into()))?,
+            process_architecture: element.value(
+                "processorArchitecture",
+                &path.appended("process_architecture".into()),
+            )?,
+            version: element.value("version", &path.appended("version".into()))?,
+            public_key_token: element
+                .value("publicKeyToken", &path.appended("public_key_token".into()))?,
+            unknown: UnknownContent::capture(
+                element,
+                |_| false,
+                &[
+                    AssemblyIdentity::ATTRIBUTE_TYPE_NAME.local_name,
+                    AssemblyIdentity::ATTRIBUTE_NAME_NAME.local_name,
+                    AssemblyIdentity::ATTRIBUTE_LANGUAGE_NAME.local_name,
+                    AssemblyIdentity::ATTRIBUTE_PROCESS_ARCHITECTURE_NAME.local_name,
+                    AssemblyIdentity::ATTRIBUTE_VERSION_NAME.local_name,
+                    AssemblyIdentity::ATTRIBUTE_PUBLIC_KEY_TOKEN_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
@@ -1,17 +1,26 @@
-use super::common::AssemblyVersion;
+use super::{
+    common::{AssemblyVersion, Guid},
+    unknown::UnknownContent,
+};
 use crate::xml_alias::{namespace, XmlAttribute, XmlName, XmlNamespace};
 use crate::{
-    consts::{NS_MS_ASM_V1, NS_MS_COMPAT_V1},
+    consts::NS_MS_COMPAT_V1,
     debug::Path,
-    serialize::{SerializableElement, SerializableValue, SerializeError, SerializeResult},
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
 };
-use std::{borrow::Cow, collections::HashSet, io::Write};
-use xml::{writer::XmlEvent, EventWriter};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
 
 /// SupportedOS
 ///
+/// Ordered by release, and custom GUIDs come last.
+/// Variants are added when Windows introduces a new context GUID, so matches need a wildcard arm.
+///
 /// reference [https://docs.microsoft.com/en-us/windows/win32/sysinfo/targeting-your-application-at-windows-8-1](https://docs.microsoft.com/en-us/windows/win32/sysinfo/targeting-your-application-at-windows-8-1)
 #[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
 pub enum SupportedOS {
     /// WindowServer2016, WindowServer2019
     Windows10,
@@ -23,37 +32,261 @@ pub enum SupportedOS {
     Windows7,
     /// WindowServer2008
     WindowsVista,
+    /// Context GUID which is not predefined.
+    ///
+    /// Use `SupportedOS::from(guid)` to map predefined GUIDs to their variants.
+    Custom(Guid),
 }
 
 impl SupportedOS {
     const ELEMENT_NAME: XmlName<'static> = XmlName {
         local_name: "supportedOS",
-        namespace: Some(NS_MS_ASM_V1),
+        namespace: Some(NS_MS_COMPAT_V1),
         prefix: None,
     };
     const ATTRIBUTE_ID_NAME: XmlName<'static> = XmlName {
         local_name: "Id",
-        namespace: Some(NS_MS_ASM_V1),
+        namespace: Some(NS_MS_COMPAT_V1),
         prefix: None,
     };
+
+    /// Windows 11 and Windows Server 2022 share the context GUID of Windows 10
+    pub const WINDOWS_11: SupportedOS = SupportedOS::Windows10;
+
+    const PREDEFINED: [SupportedOS; 5] = [
+        SupportedOS::WindowsVista,
+        SupportedOS::Windows7,
+        SupportedOS::Windows8,
+        SupportedOS::Windows8_1,
+        SupportedOS::Windows10,
+    ];
+
+    /// Context GUID of OS
+    pub const fn guid(&self) -> Guid {
+        match self {
+            SupportedOS::Windows10 => Guid::from_u128(0x8e0f7a12_bfb3_4fe8_b9a5_48fd50a15a9a),
+            SupportedOS::Windows8_1 => Guid::from_u128(0x1f676c76_80e1_4239_95bb_83d0f6d0da78),
+            SupportedOS::Windows8 => Guid::from_u128(0x4a2f28e3_53b9_4441_ba9c_d69d4a4a6e38),
+            SupportedOS::Windows7 => Guid::from_u128(0x35138b9a_5d96_4fbd_8e2d_a2440225f93a),
+            SupportedOS::WindowsVista => Guid::from_u128(0xe2011457_1546_43c5_a5fe_008deee3d3f0),
+            SupportedOS::Custom(guid) => *guid,
+        }
+    }
+
+    /// Canonical name, which is accepted by `FromStr`. `None` for custom GUIDs
+    pub const fn name(&self) -> Option<&'static str> {
+        match self {
+            SupportedOS::Windows10 => Some("Windows10"),
+            SupportedOS::Windows8_1 => Some("Windows8.1"),
+            SupportedOS::Windows8 => Some("Windows8"),
+            SupportedOS::Windows7 => Some("Windows7"),
+            SupportedOS::WindowsVista => Some("WindowsVista"),
+            SupportedOS::Custom(_) => None,
+        }
+    }
+
+    /// Version of first release of OS, `None` for custom GUIDs
+    pub const fn version(&self) -> Option<AssemblyVersion> {
+        match self {
+            SupportedOS::Windows10 => Some(windows_version::WINDOWS_10_1507),
+            SupportedOS::Windows8_1 => Some(AssemblyVersion::new(6, 3, 9600, Some(0))),
+            SupportedOS::Windows8 => Some(AssemblyVersion::new(6, 2, 9200, Some(0))),
+            SupportedOS::Windows7 => Some(AssemblyVersion::new(6, 1, 7600, Some(0))),
+            SupportedOS::WindowsVista => Some(AssemblyVersion::new(6, 0, 6000, Some(0))),
+            SupportedOS::Custom(_) => None,
+        }
+    }
+
+    fn release_order(&self) -> (usize, Option<Guid>) {
+        match self {
+            SupportedOS::Custom(guid) => (SupportedOS::PREDEFINED.len(), Some(*guid)),
+            os => (
+                SupportedOS::PREDEFINED
+                    .iter()
+                    .position(|predefined| predefined == os)
+                    .unwrap_or_default(),
+                None,
+            ),
+        }
+    }
+}
+
+impl From<Guid> for SupportedOS {
+    fn from(guid: Guid) -> Self {
+        SupportedOS::PREDEFINED
+            .iter()
+            .copied()
+            .find(|os| os.guid() == guid)
+            .unwrap_or(SupportedOS::Custom(guid))
+    }
+}
+
+impl PartialOrd for SupportedOS {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SupportedOS {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.release_order().cmp(&other.release_order())
+    }
+}
+
+/// Error of parsing [`SupportedOS`](enum.SupportedOS.html)
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+#[error("unknown OS - {0}")]
+pub struct SupportedOSParseError(String);
+
+impl std::str::FromStr for SupportedOS {
+    type Err = SupportedOSParseError;
+
+    /// Parse friendly name such as `win10`, `Windows 8.1`, `vista` or `server2022`, or context GUID
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(guid) = s.parse::<Guid>() {
+            return Ok(SupportedOS::from(guid));
+        }
+
+        let name = s
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '_' | '-'))
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let name = name
+            .strip_prefix("windows")
+            .or_else(|| name.strip_prefix("win"))
+            .unwrap_or(&name);
+        match name {
+            "10" | "11" | "server2016" | "server2019" | "server2022" => Ok(SupportedOS::Windows10),
+            "8.1" | "81" | "server2012r2" => Ok(SupportedOS::Windows8_1),
+            "8" | "server2012" => Ok(SupportedOS::Windows8),
+            "7" | "server2008r2" => Ok(SupportedOS::Windows7),
+            "vista" | "server2008" => Ok(SupportedOS::WindowsVista),
+            _ => Err(SupportedOSParseError(s.to_string())),
+        }
+    }
 }
 
 impl SerializableValue for SupportedOS {
     fn serialize(&self) -> Cow<'_, str> {
-        Cow::Borrowed(match self {
-            SupportedOS::Windows10 => "{8e0f7a12-bfb3-4fe8-b9a5-48fd50a15a9a}",
-            SupportedOS::Windows8_1 => "{1f676c76-80e1-4239-95bb-83d0f6d0da78}",
-            SupportedOS::Windows8 => "{4a2f28e3-53b9-4441-ba9c-d69d4a4a6e38}",
-            SupportedOS::Windows7 => "{35138b9a-5d96-4fbd-8e2d-a2440225f93a}",
-            SupportedOS::WindowsVista => "{e2011457-1546-43c5-a5fe-008deee3d3f0}",
-        })
+        Cow::Owned(self.guid().to_string())
+    }
+}
+
+impl DeserializableValue for SupportedOS {
+    fn deserialize(value: &str) -> Option<Self> {
+        value.parse::<Guid>().ok().map(SupportedOS::from)
+    }
+}
+
+/// Set of [`SupportedOS`](enum.SupportedOS.html) which remembers insertion order
+///
+/// Equality ignores order.
+#[derive(Debug, Default, Eq, Clone)]
+pub struct SupportedOSSet(Vec<SupportedOS>);
+
+impl SupportedOSSet {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        SupportedOSSet(Vec::new())
+    }
+
+    /// Add OS. Returns `false` if it is already present.
+    pub fn insert(&mut self, os: SupportedOS) -> bool {
+        if self.contains(&os) {
+            false
+        } else {
+            self.0.push(os);
+            true
+        }
+    }
+
+    /// Remove OS. Returns `false` if it is not present.
+    pub fn remove(&mut self, os: &SupportedOS) -> bool {
+        let len = self.0.len();
+        self.0.retain(|item| item != os);
+        self.0.len() != len
+    }
+
+    #[allow(missing_docs)]
+    pub fn contains(&self, os: &SupportedOS) -> bool {
+        self.0.contains(os)
+    }
+
+    #[allow(missing_docs)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[allow(missing_docs)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate in insertion order
+    pub fn iter(&self) -> std::slice::Iter<'_, SupportedOS> {
+        self.0.iter()
+    }
+
+    /// OS list in given order
+    pub fn ordered(&self, order: SupportedOSOrder) -> Vec<SupportedOS> {
+        let mut list = self.0.clone();
+        if order == SupportedOSOrder::NewestFirst {
+            list.sort_by(|a, b| b.cmp(a));
+        }
+        list
+    }
+}
+
+impl PartialEq for SupportedOSSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|os| other.contains(os))
+    }
+}
+
+impl<'a> IntoIterator for &'a SupportedOSSet {
+    type Item = &'a SupportedOS;
+    type IntoIter = std::slice::Iter<'a, SupportedOS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Extend<SupportedOS> for SupportedOSSet {
+    fn extend<I: IntoIterator<Item = SupportedOS>>(&mut self, iter: I) {
+        for os in iter {
+            self.insert(os);
+        }
+    }
+}
+
+impl std::iter::FromIterator<SupportedOS> for SupportedOSSet {
+    fn from_iter<I: IntoIterator<Item = SupportedOS>>(iter: I) -> Self {
+        let mut set = SupportedOSSet::new();
+        set.extend(iter);
+        set
     }
 }
 
+/// Order of `supportedOS` elements in serialized manifest
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum SupportedOSOrder {
+    /// Newest OS first, custom GUIDs before predefined ones. Same as Visual Studio.
+    #[default]
+    NewestFirst,
+    /// Order of insertion
+    Insertion,
+}
+
 /// Predefined Windows versions
 ///
 /// This versions are used with [`Compatibility::max_version_tested`](../struct.Compatibility.html#structfield.max_version_tested)
 ///
+/// [`RELEASES`](constant.RELEASES.html) is a catalogue of known releases, which can be queried by product, marketing name and build number.
+///
 /// reference: [https://docs.microsoft.com/en-us/windows/release-information/](https://docs.microsoft.com/en-us/windows/release-information/)
 pub mod windows_version {
     #![allow(missing_docs)]
@@ -66,22 +299,136 @@ pub mod windows_version {
     pub const WINDOWS_10_1803: AssemblyVersion = AssemblyVersion::new(10, 0, 17134, Some(0));
     pub const WINDOWS_10_1809: AssemblyVersion = AssemblyVersion::new(10, 0, 17763, Some(0));
     pub const WINDOWS_10_1903: AssemblyVersion = AssemblyVersion::new(10, 0, 18362, Some(0));
+    pub const WINDOWS_10_1909: AssemblyVersion = AssemblyVersion::new(10, 0, 18363, Some(0));
     pub const WINDOWS_10_2004: AssemblyVersion = AssemblyVersion::new(10, 0, 19041, Some(0));
+    pub const WINDOWS_10_20H2: AssemblyVersion = AssemblyVersion::new(10, 0, 19042, Some(0));
+    pub const WINDOWS_10_21H1: AssemblyVersion = AssemblyVersion::new(10, 0, 19043, Some(0));
+    pub const WINDOWS_10_21H2: AssemblyVersion = AssemblyVersion::new(10, 0, 19044, Some(0));
+    pub const WINDOWS_10_22H2: AssemblyVersion = AssemblyVersion::new(10, 0, 19045, Some(0));
+    pub const WINDOWS_11_21H2: AssemblyVersion = AssemblyVersion::new(10, 0, 22000, Some(0));
+    pub const WINDOWS_11_22H2: AssemblyVersion = AssemblyVersion::new(10, 0, 22621, Some(0));
+    pub const WINDOWS_11_23H2: AssemblyVersion = AssemblyVersion::new(10, 0, 22631, Some(0));
+    pub const WINDOWS_11_24H2: AssemblyVersion = AssemblyVersion::new(10, 0, 26100, Some(0));
+    pub const WINDOWS_SERVER_2016: AssemblyVersion = AssemblyVersion::new(10, 0, 14393, Some(0));
+    pub const WINDOWS_SERVER_2019: AssemblyVersion = AssemblyVersion::new(10, 0, 17763, Some(0));
+    pub const WINDOWS_SERVER_2022: AssemblyVersion = AssemblyVersion::new(10, 0, 20348, Some(0));
+    pub const WINDOWS_SERVER_2025: AssemblyVersion = AssemblyVersion::new(10, 0, 26100, Some(0));
+
+    /// Product line of Windows release
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum Product {
+        Windows10,
+        Windows11,
+        WindowsServer,
+    }
+
+    /// Known release of Windows
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub struct Release {
+        pub product: Product,
+        /// Marketing name, such as `22H2` or `2022` for servers
+        pub name: &'static str,
+        pub version: AssemblyVersion,
+    }
+
+    const fn release(product: Product, name: &'static str, version: AssemblyVersion) -> Release {
+        Release {
+            product,
+            name,
+            version,
+        }
+    }
+
+    /// Known releases, ordered by product and release date
+    pub const RELEASES: &[Release] = &[
+        release(Product::Windows10, "1507", WINDOWS_10_1507),
+        release(Product::Windows10, "1511", WINDOWS_10_1511),
+        release(Product::Windows10, "1607", WINDOWS_10_1607),
+        release(Product::Windows10, "1703", WINDOWS_10_1703),
+        release(Product::Windows10, "1709", WINDOWS_10_1709),
+        release(Product::Windows10, "1803", WINDOWS_10_1803),
+        release(Product::Windows10, "1809", WINDOWS_10_1809),
+        release(Product::Windows10, "1903", WINDOWS_10_1903),
+        release(Product::Windows10, "1909", WINDOWS_10_1909),
+        release(Product::Windows10, "2004", WINDOWS_10_2004),
+        release(Product::Windows10, "20H2", WINDOWS_10_20H2),
+        release(Product::Windows10, "21H1", WINDOWS_10_21H1),
+        release(Product::Windows10, "21H2", WINDOWS_10_21H2),
+        release(Product::Windows10, "22H2", WINDOWS_10_22H2),
+        release(Product::Windows11, "21H2", WINDOWS_11_21H2),
+        release(Product::Windows11, "22H2", WINDOWS_11_22H2),
+        release(Product::Windows11, "23H2", WINDOWS_11_23H2),
+        release(Product::Windows11, "24H2", WINDOWS_11_24H2),
+        release(Product::WindowsServer, "2016", WINDOWS_SERVER_2016),
+        release(Product::WindowsServer, "2019", WINDOWS_SERVER_2019),
+        release(Product::WindowsServer, "2022", WINDOWS_SERVER_2022),
+        release(Product::WindowsServer, "2025", WINDOWS_SERVER_2025),
+    ];
+
+    /// Release of product by marketing name, case-insensitively
+    pub fn find(product: Product, name: &str) -> Option<&'static Release> {
+        by_name(name).find(|release| release.product == product)
+    }
+
+    /// Releases of any product by marketing name, case-insensitively
+    pub fn by_name<'a>(name: &'a str) -> impl Iterator<Item = &'static Release> + 'a {
+        RELEASES
+            .iter()
+            .filter(move |release| release.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Releases of product
+    pub fn by_product(product: Product) -> impl Iterator<Item = &'static Release> {
+        RELEASES
+            .iter()
+            .filter(move |release| release.product == product)
+    }
+
+    /// Releases with build number. Client and server releases can share a build.
+    pub fn by_build(build: u32) -> impl Iterator<Item = &'static Release> {
+        RELEASES
+            .iter()
+            .filter(move |release| release.version.build == build)
+    }
+
+    /// Latest known release of product
+    pub fn latest(product: Product) -> &'static Release {
+        by_product(product)
+            .max_by_key(|release| release.version)
+            .expect("every product has releases")
+    }
+
+    /// Latest known release of any product
+    pub fn latest_known() -> &'static Release {
+        RELEASES
+            .iter()
+            .max_by_key(|release| release.version)
+            .expect("catalogue is not empty")
+    }
 }
 
 /// Compatibility info about assembly
 #[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Compatibility {
     /// Supported os
-    pub supported_os: HashSet<SupportedOS>,
-    /// Maximum version of Windows thatt the application tested against.
+    pub supported_os: SupportedOSSet,
+    /// Order of `supported_os` in serialized manifest
+    pub supported_os_order: SupportedOSOrder,
+    /// Maximum versions of Windows that the application tested against, such as one for each product line.
     ///
     /// This value is required to use [XAML Islands](https://docs.microsoft.com/en-us/windows/apps/desktop/modernize/xaml-islands).
-    pub max_version_tested: Option<AssemblyVersion>,
+    pub max_version_tested: Vec<AssemblyVersion>,
+    /// Content of `compatibility` element which is not modeled.
+    ///
+    /// Content of inner `application` element is kept in [`UnknownContent::nested`](struct.UnknownContent.html#structfield.nested).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
 }
 
 impl Compatibility {
-    const ELEMENT_NAME: XmlName<'static> = XmlName {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
         local_name: "compatibility",
         namespace: Some(NS_MS_COMPAT_V1),
         prefix: None,
@@ -103,40 +450,70 @@ impl Compatibility {
     };
 }
 
+impl Validatable for Compatibility {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        let max_version_tested_path = path.appended("max_version_tested".into());
+        if self.supported_os.is_empty()
+            && self.unknown.is_empty()
+            && !self.max_version_tested.is_empty()
+        {
+            problems.error(
+                &max_version_tested_path,
+                "max-version-without-supported-os",
+                "max_version_tested requires at least one supported_os",
+                "add SupportedOS::Windows10 to supported_os",
+            );
+        }
+
+        for (idx, maxversion) in self.max_version_tested.iter().enumerate() {
+            let path = max_version_tested_path.appended(idx.into());
+            if self.max_version_tested[..idx].contains(maxversion) {
+                problems.error(
+                    &path,
+                    "duplicate-max-version-tested",
+                    format!("max_version_tested is duplicated - {}", maxversion),
+                    "remove duplicated version",
+                );
+            }
+            maxversion.validate(path, problems);
+        }
+    }
+}
+
 impl SerializableElement for Compatibility {
-    fn serialize<W: Write>(
-        &self,
-        writer: &mut EventWriter<W>,
-        path: Path<'_>,
-    ) -> SerializeResult<()> {
-        if self.supported_os.is_empty() {
-            return if self.max_version_tested.is_some() {
-                Err(SerializeError::Invalid {
-                    path: format!("{}.{}", &path, "maxversion_tested"),
-                    detail: "maxversion_tested requires at least one supported_os".to_string(),
-                })?
-            } else {
-                Ok(())
-            };
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        if self.supported_os.is_empty() && self.unknown.is_empty() {
+            return Ok(());
         }
 
+        let unknown_path = path.appended("unknown".into());
+        let mut attributes = Vec::new();
+        self.unknown.extend_attributes(&mut attributes);
         writer.write(XmlEvent::StartElement {
             name: Compatibility::ELEMENT_NAME,
-            attributes: Cow::Borrowed(&[]),
-            namespace: Cow::Owned({
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace({
                 let mut ns = XmlNamespace::empty();
                 ns.put(namespace::NS_NO_PREFIX.to_string(), NS_MS_COMPAT_V1);
                 ns
-            }),
+            })),
         })?;
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
 
+        let application = self
+            .unknown
+            .nested(Compatibility::ELEMENT_APPLICATTION_NAME.local_name);
+        let application_path = unknown_path.appended("application".into());
+        let mut attributes = Vec::new();
+        application.extend_attributes(&mut attributes);
         writer.write(XmlEvent::StartElement {
             name: Compatibility::ELEMENT_APPLICATTION_NAME,
-            attributes: Cow::Borrowed(&[]),
-            namespace: Cow::Owned(XmlNamespace::empty()),
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(application.namespace(XmlNamespace::empty())),
         })?;
+        application.serialize_nodes(writer, &application_path, None)?;
 
-        if let Some(maxversion) = &self.max_version_tested {
+        for maxversion in &self.max_version_tested {
             writer.write(XmlEvent::StartElement {
                 name: Compatibility::ELEMENT_MAXVERSION_TESTED_NAME,
                 attributes: Cow::Borrowed(&[XmlAttribute {
@@ -147,8 +524,13 @@ impl SerializableElement for Compatibility {
             })?;
             writer.write(XmlEvent::EndElement { name: None })?;
         }
+        application.serialize_nodes(
+            writer,
+            &application_path,
+            Some(Compatibility::ELEMENT_MAXVERSION_TESTED_NAME.local_name),
+        )?;
 
-        for os in &self.supported_os {
+        for os in self.supported_os.ordered(self.supported_os_order) {
             writer.write(XmlEvent::StartElement {
                 name: SupportedOS::ELEMENT_NAME,
                 attributes: Cow::Borrowed(&[XmlAttribute {
@@ -159,11 +541,77 @@ impl SerializableElement for Compatibility {
             })?;
             writer.write(XmlEvent::EndElement { name: None })?;
         }
+        application.serialize_nodes(
+            writer,
+            &application_path,
+            Some(SupportedOS::ELEMENT_NAME.local_name),
+        )?;
 
         writer.write(XmlEvent::EndElement { name: None })?;
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(Compatibility::ELEMENT_APPLICATTION_NAME.local_name),
+        )?;
 
         writer.write(XmlEvent::EndElement { name: None })?;
 
         Ok(())
     }
 }
+
+impl DeserializableElement for Compatibility {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let mut compatibility = Compatibility::default();
+        compatibility.deserialize_merge(element, path)?;
+        Ok(compatibility)
+    }
+}
+
+impl Compatibility {
+    /// Merge `compatibility` element, which can be repeated in manifest
+    pub(crate) fn deserialize_merge(
+        &mut self,
+        element: &Element,
+        path: Path<'_>,
+    ) -> ParseResult<()> {
+        let compatibility = self;
+        compatibility.unknown.merge(UnknownContent::capture(
+            element,
+            |child| child.is(&Compatibility::ELEMENT_APPLICATTION_NAME),
+            &[],
+        ));
+
+        let supported_os_path = path.appended("supported_os".into());
+        let max_version_tested_path = path.appended("max_version_tested".into());
+        for application in element
+            .elements()
+            .filter(|child| child.is(&Compatibility::ELEMENT_APPLICATTION_NAME))
+        {
+            compatibility.unknown.capture_nested(
+                application,
+                |child| {
+                    child.is(&SupportedOS::ELEMENT_NAME)
+                        || child.is(&Compatibility::ELEMENT_MAXVERSION_TESTED_NAME)
+                },
+                &[],
+            );
+            for child in application.elements() {
+                if child.is(&SupportedOS::ELEMENT_NAME) {
+                    let path = supported_os_path.appended(compatibility.supported_os.len().into());
+                    compatibility
+                        .supported_os
+                        .insert(child.required_value("Id", &path)?);
+                } else if child.is(&Compatibility::ELEMENT_MAXVERSION_TESTED_NAME) {
+                    let path = max_version_tested_path
+                        .appended(compatibility.max_version_tested.len().into());
+                    compatibility
+                        .max_version_tested
+                        .push(child.required_value("Id", &path)?);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,58 +1,385 @@
-use super::common::AssemblyIdentity;
+use super::{
+    common::{AssemblyIdentity, AssemblyVersion, YesNo},
+    unknown::UnknownContent,
+};
 use crate::{
     consts::NS_MS_ASM_V1,
     debug::Path,
-    serialize::{SerializableElement, SerializeResult},
-    xml_alias::{XmlName, XmlNamespace},
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+    xml_alias::{XmlAttribute, XmlName, XmlNamespace},
 };
-use std::{borrow::Cow, io::Write};
-use xml::{writer::XmlEvent, EventWriter};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
+
+/// Inclusive range of assembly versions, written as `1.0.0.0-1.9.9.9`
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct VersionRange {
+    #[allow(missing_docs)]
+    pub start: AssemblyVersion,
+    #[allow(missing_docs)]
+    pub end: AssemblyVersion,
+}
+
+impl VersionRange {
+    #[allow(missing_docs)]
+    pub const fn new(start: AssemblyVersion, end: AssemblyVersion) -> Self {
+        VersionRange { start, end }
+    }
+
+    /// Range which contains only one version
+    pub const fn single(version: AssemblyVersion) -> Self {
+        VersionRange {
+            start: version,
+            end: version,
+        }
+    }
+}
+
+impl SerializableValue for VersionRange {
+    fn serialize(&self) -> Cow<'_, str> {
+        if self.start == self.end {
+            self.start.serialize()
+        } else {
+            Cow::Owned(format!(
+                "{}-{}",
+                self.start.serialize(),
+                self.end.serialize()
+            ))
+        }
+    }
+}
+
+impl DeserializableValue for VersionRange {
+    fn deserialize(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(2, '-');
+        let start = AssemblyVersion::deserialize(parts.next()?.trim())?;
+        let end = match parts.next() {
+            Some(end) => AssemblyVersion::deserialize(end.trim())?,
+            None => start,
+        };
+        Some(VersionRange { start, end })
+    }
+}
+
+/// Redirect of versions of dependent assembly (`bindingRedirect`)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BindingRedirect {
+    /// Versions which are redirected
+    pub old_version: VersionRange,
+    /// Version which is loaded instead
+    pub new_version: AssemblyVersion,
+}
+
+impl BindingRedirect {
+    const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "bindingRedirect",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_OLD_VERSION_NAME: XmlName<'static> = XmlName {
+        local_name: "oldVersion",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NEW_VERSION_NAME: XmlName<'static> = XmlName {
+        local_name: "newVersion",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+}
+
+impl Validatable for BindingRedirect {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        let old_version_path = path.appended("old_version".into());
+        self.old_version
+            .start
+            .validate(old_version_path.appended("start".into()), problems);
+        self.old_version
+            .end
+            .validate(old_version_path.appended("end".into()), problems);
+        self.new_version
+            .validate(path.appended("new_version".into()), problems);
+        if self.old_version.start > self.old_version.end {
+            problems.error(
+                &old_version_path,
+                "binding-redirect-range",
+                format!(
+                    "start of range is greater than end - {}",
+                    self.old_version.serialize()
+                ),
+                "swap start and end of range",
+            );
+        }
+    }
+}
+
+impl SerializableElement for BindingRedirect {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, _path: Path<'_>) -> SerializeResult<()> {
+        writer.write(XmlEvent::StartElement {
+            name: BindingRedirect::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&[
+                XmlAttribute {
+                    name: BindingRedirect::ATTRIBUTE_OLD_VERSION_NAME,
+                    value: &self.old_version.serialize(),
+                },
+                XmlAttribute {
+                    name: BindingRedirect::ATTRIBUTE_NEW_VERSION_NAME,
+                    value: &self.new_version.serialize(),
+                },
+            ]),
+            namespace: Cow::Owned(XmlNamespace::empty()),
+        })?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for BindingRedirect {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        Ok(BindingRedirect {
+            old_version: element
+                .required_value("oldVersion", &path.appended("old_version".into()))?,
+            new_version: element
+                .required_value("newVersion", &path.appended("new_version".into()))?,
+        })
+    }
+}
+
+/// Dependent assembly, written as `dependentAssembly` element
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DependentAssembly {
+    #[allow(missing_docs)]
+    pub identity: AssemblyIdentity,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub binding_redirects: Vec<BindingRedirect>,
+    /// Content of `dependentAssembly` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl DependentAssembly {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "dependentAssembly",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new(identity: AssemblyIdentity) -> Self {
+        DependentAssembly {
+            identity,
+            binding_redirects: Vec::new(),
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl From<AssemblyIdentity> for DependentAssembly {
+    fn from(identity: AssemblyIdentity) -> Self {
+        DependentAssembly::new(identity)
+    }
+}
+
+impl Validatable for DependentAssembly {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        let identity_path = path.appended("identity".into());
+        if self.identity.version.is_none() {
+            problems.warning(
+                &identity_path.appended("version".into()),
+                "dependency-without-version",
+                "dependent assembly without version can't be resolved from side-by-side store",
+                "set version of dependent assembly",
+            );
+        }
+        self.identity.validate(identity_path, problems);
+        self.binding_redirects
+            .validate(path.appended("binding_redirects".into()), problems);
+    }
+}
+
+impl SerializableElement for DependentAssembly {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let unknown_path = path.appended("unknown".into());
+        let mut attributes = Vec::<XmlAttribute>::new();
+        self.unknown.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: DependentAssembly::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        self.identity
+            .serialize(writer, path.appended("identity".into()))?;
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(AssemblyIdentity::ELEMENT_NAME.local_name),
+        )?;
+
+        let binding_redirects_path = path.appended("binding_redirects".into());
+        for (idx, binding_redirect) in self.binding_redirects.iter().enumerate() {
+            binding_redirect.serialize(writer, binding_redirects_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(BindingRedirect::ELEMENT_NAME.local_name),
+        )?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
 
-/// Dependencies of assembly
+impl DeserializableElement for DependentAssembly {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let identity = element
+            .elements()
+            .find(|child| child.is(&AssemblyIdentity::ELEMENT_NAME))
+            .ok_or_else(|| element.invalid(&path, "dependentAssembly requires assemblyIdentity"))?;
+        let binding_redirects_path = path.appended("binding_redirects".into());
+
+        Ok(DependentAssembly {
+            identity: AssemblyIdentity::deserialize(identity, path.appended("identity".into()))?,
+            binding_redirects: element
+                .elements()
+                .filter(|child| child.is(&BindingRedirect::ELEMENT_NAME))
+                .enumerate()
+                .map(|(idx, redirect)| {
+                    BindingRedirect::deserialize(
+                        redirect,
+                        binding_redirects_path.appended(idx.into()),
+                    )
+                })
+                .collect::<ParseResult<_>>()?,
+            unknown: UnknownContent::capture(
+                element,
+                |child| {
+                    child.is(&AssemblyIdentity::ELEMENT_NAME)
+                        || child.is(&BindingRedirect::ELEMENT_NAME)
+                },
+                &[],
+            ),
+        })
+    }
+}
+
+/// Dependency of assembly, written as `dependency` element
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Dependency {
-    /// Dependent assemblies
-    pub dependent_assemblies: Vec<AssemblyIdentity>,
+    /// Dependent assemblies, which is usually only one
+    pub dependent_assemblies: Vec<DependentAssembly>,
+    /// Whether the application can run without these assemblies
+    pub optional: Option<bool>,
+    /// Content of `dependency` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
 }
 
 impl Dependency {
-    const ELEMENT_NAME: XmlName<'static> = XmlName {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
         local_name: "dependency",
         namespace: Some(NS_MS_ASM_V1),
         prefix: None,
     };
 
-    const ELEMENT_ASSEMBLY_NAME: XmlName<'static> = XmlName {
-        local_name: "dependentAssembly",
+    const ATTRIBUTE_OPTIONAL_NAME: XmlName<'static> = XmlName {
+        local_name: "optional",
         namespace: Some(NS_MS_ASM_V1),
         prefix: None,
     };
 }
 
+impl From<DependentAssembly> for Dependency {
+    fn from(dependent_assembly: DependentAssembly) -> Self {
+        Dependency {
+            dependent_assemblies: vec![dependent_assembly],
+            ..Dependency::default()
+        }
+    }
+}
+
+impl From<AssemblyIdentity> for Dependency {
+    fn from(identity: AssemblyIdentity) -> Self {
+        Dependency::from(DependentAssembly::new(identity))
+    }
+}
+
+impl Validatable for Dependency {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        self.dependent_assemblies
+            .validate(path.appended("dependent_assemblies".into()), problems);
+    }
+}
+
 impl SerializableElement for Dependency {
-    fn serialize<W: Write>(
-        &self,
-        writer: &mut EventWriter<W>,
-        path: Path<'_>,
-    ) -> SerializeResult<()> {
-        for (idx, assembly) in (&self.dependent_assemblies).into_iter().enumerate() {
-            writer.write(XmlEvent::StartElement {
-                name: Dependency::ELEMENT_NAME,
-                attributes: Cow::Borrowed(&[]),
-                namespace: Cow::Borrowed(&XmlNamespace::empty()),
-            })?;
-            writer.write(XmlEvent::StartElement {
-                name: Dependency::ELEMENT_ASSEMBLY_NAME,
-                attributes: Cow::Borrowed(&[]),
-                namespace: Cow::Borrowed(&XmlNamespace::empty()),
-            })?;
-
-            assembly.serialize(writer, path.appended(idx.into()))?;
-
-            writer.write(XmlEvent::EndElement { name: None })?;
-            writer.write(XmlEvent::EndElement { name: None })?;
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let unknown_path = path.appended("unknown".into());
+        let optional = self.optional.map(YesNo);
+        let optional = optional.as_ref().map(|optional| optional.serialize());
+        let mut attributes = Vec::<XmlAttribute>::new();
+        if let Some(optional) = &optional {
+            attributes.push(XmlAttribute {
+                name: Dependency::ATTRIBUTE_OPTIONAL_NAME,
+                value: optional,
+            });
         }
+        self.unknown.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: Dependency::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        let dependent_assemblies_path = path.appended("dependent_assemblies".into());
+        for (idx, assembly) in self.dependent_assemblies.iter().enumerate() {
+            assembly.serialize(writer, dependent_assemblies_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(DependentAssembly::ELEMENT_NAME.local_name),
+        )?;
+        writer.write(XmlEvent::EndElement { name: None })?;
 
         Ok(())
     }
 }
+
+impl DeserializableElement for Dependency {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let dependent_assemblies_path = path.appended("dependent_assemblies".into());
+
+        Ok(Dependency {
+            dependent_assemblies: element
+                .elements()
+                .filter(|child| child.is(&DependentAssembly::ELEMENT_NAME))
+                .enumerate()
+                .map(|(idx, assembly)| {
+                    DependentAssembly::deserialize(
+                        assembly,
+                        dependent_assemblies_path.appended(idx.into()),
+                    )
+                })
+                .collect::<ParseResult<_>>()?,
+            optional: element
+                .value::<YesNo>("optional", &path.appended("optional".into()))?
+                .map(|optional| optional.0),
+            unknown: UnknownContent::capture(
+                element,
+                |child| child.is(&DependentAssembly::ELEMENT_NAME),
+                &[Dependency::ATTRIBUTE_OPTIONAL_NAME.local_name],
+            ),
+        })
+    }
+}
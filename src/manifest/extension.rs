@@ -0,0 +1,180 @@
+use super::{unknown::UnknownNode, AssemblyManifest};
+use crate::{
+    debug::Path,
+    deserialize::{Element, Node, ParseResult},
+    serialize::{SerializableElement, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+};
+use std::{io::Write, sync::Arc};
+use xml::writer::EmitterConfig;
+
+/// Well-formed XML fragment, which is written as is
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct XmlFragment {
+    nodes: Vec<UnknownNode>,
+}
+
+impl XmlFragment {
+    /// Parse `xml` with namespaces declared as pairs of prefix and URI.
+    ///
+    /// Every top-level element must be in one of the declared namespaces.
+    pub fn new<S: AsRef<str>>(xml: S, namespaces: &[(&str, &str)]) -> ParseResult<Self> {
+        let mut document = String::from("<fragment");
+        for (prefix, uri) in namespaces {
+            if prefix.is_empty() {
+                document.push_str(" xmlns");
+            } else {
+                document.push_str(" xmlns:");
+                document.push_str(prefix);
+            }
+            document.push_str("=\"");
+            document.push_str(&escape_attribute(uri));
+            document.push('"');
+        }
+        document.push('>');
+        document.push_str(xml.as_ref());
+        document.push_str("</fragment>");
+
+        let root = Element::read_document(document.as_bytes())?.root;
+        let path = Path::new("fragment".into());
+        let mut nodes = Vec::new();
+        for child in &root.children {
+            if let Node::Element(element) = child {
+                let namespace = element.name.namespace.as_deref();
+                if !namespaces.iter().any(|(_, uri)| Some(*uri) == namespace) {
+                    return Err(element.invalid(
+                        &path,
+                        format!("{} is not in declared namespace", element.name.local_name),
+                    ));
+                }
+            }
+            let mut node = UnknownNode::capture(child);
+            if let UnknownNode::Element(element) = &mut node {
+                // Declarations of the wrapper must be kept on each top-level element
+                for (prefix, uri) in namespaces {
+                    if !element
+                        .namespaces
+                        .iter()
+                        .any(|(declared, _)| declared == prefix)
+                    {
+                        element
+                            .namespaces
+                            .push((prefix.to_string(), uri.to_string()));
+                    }
+                }
+            }
+            nodes.push(node);
+        }
+
+        Ok(XmlFragment { nodes })
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
+
+impl SerializableElement for XmlFragment {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        for (idx, node) in self.nodes.iter().enumerate() {
+            node.serialize(writer, path.appended(idx.into()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Content which is not modeled by this crate, added by user.
+///
+/// Extensions are written at the end of `assembly`, in order of the list.
+#[derive(Debug, Clone)]
+pub enum Extension {
+    /// Custom element.
+    ///
+    /// Elements are equal only when they are the same instance.
+    Element(Arc<dyn SerializableElement + Send + Sync>),
+    #[allow(missing_docs)]
+    Fragment(XmlFragment),
+}
+
+impl Extension {
+    /// Extension of custom element
+    pub fn element<E: SerializableElement + Send + Sync + 'static>(element: E) -> Self {
+        Extension::Element(Arc::new(element))
+    }
+}
+
+impl From<Box<dyn SerializableElement + Send + Sync>> for Extension {
+    fn from(element: Box<dyn SerializableElement + Send + Sync>) -> Self {
+        Extension::Element(Arc::from(element))
+    }
+}
+
+impl From<XmlFragment> for Extension {
+    fn from(fragment: XmlFragment) -> Self {
+        Extension::Fragment(fragment)
+    }
+}
+
+impl PartialEq for Extension {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Extension::Element(lhs), Extension::Element(rhs)) => Arc::ptr_eq(lhs, rhs),
+            (Extension::Fragment(lhs), Extension::Fragment(rhs)) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Extension {}
+
+impl SerializableElement for Extension {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        match self {
+            Extension::Element(element) => element.serialize(writer, path),
+            Extension::Fragment(fragment) => fragment.serialize(writer, path),
+        }
+    }
+}
+
+impl Validatable for Extension {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        match self {
+            // Custom elements can be checked only by writing them, so they are written to nowhere
+            Extension::Element(element) => {
+                let mut sink = std::io::sink();
+                let mut writer = EmitterConfig::new()
+                    .write_document_declaration(false)
+                    .create_writer(&mut sink as &mut dyn Write);
+                if let Err(err) = element.serialize(&mut writer, path) {
+                    problems.error(
+                        &path,
+                        "extension-write-failed",
+                        format!("custom element can't be written - {}", err),
+                        "fix SerializableElement implementation of the element",
+                    );
+                }
+            }
+            Extension::Fragment(fragment) => {
+                for (idx, node) in fragment.nodes.iter().enumerate() {
+                    if let UnknownNode::Element(element) = node {
+                        if AssemblyManifest::is_known_name(&element.name) {
+                            problems.warning(
+                                &path.appended(idx.into()),
+                                "extension-modeled-element",
+                                format!(
+                                    "{} is modeled by AssemblyManifest, and may be written twice",
+                                    element.name.local_name
+                                ),
+                                "set the field of AssemblyManifest instead of adding extension",
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1093 @@
+use super::{
+    common::{from_hex, to_hex, Guid, YesNo},
+    unknown::UnknownContent,
+};
+use crate::xml_alias::{XmlAttribute, XmlName, XmlNamespace};
+use crate::{
+    consts::NS_MS_ASM_V1,
+    debug::Path,
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
+
+/// Hash algorithm of [`File::hash`](struct.File.html#structfield.hash)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum HashAlgorithm {
+    #[allow(missing_docs)]
+    Sha1,
+    /// Same as SHA1
+    Sha,
+    #[allow(missing_docs)]
+    Md5,
+    #[allow(missing_docs)]
+    Md4,
+    #[allow(missing_docs)]
+    Md2,
+}
+
+impl HashAlgorithm {
+    /// Length of digest in bytes
+    pub fn digest_len(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha1 | HashAlgorithm::Sha => 20,
+            HashAlgorithm::Md5 | HashAlgorithm::Md4 | HashAlgorithm::Md2 => 16,
+        }
+    }
+}
+
+impl SerializableValue for HashAlgorithm {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            HashAlgorithm::Sha1 => "SHA1",
+            HashAlgorithm::Sha => "SHA",
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Md4 => "MD4",
+            HashAlgorithm::Md2 => "MD2",
+        })
+    }
+}
+
+impl DeserializableValue for HashAlgorithm {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "SHA1" => Some(HashAlgorithm::Sha1),
+            "SHA" => Some(HashAlgorithm::Sha),
+            "MD5" => Some(HashAlgorithm::Md5),
+            "MD4" => Some(HashAlgorithm::Md4),
+            "MD2" => Some(HashAlgorithm::Md2),
+            _ => None,
+        }
+    }
+}
+
+/// COM threading model
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ThreadingModel {
+    /// Single-threaded apartment
+    Apartment,
+    /// Multi-threaded apartment
+    Free,
+    /// Both of single-threaded and multi-threaded apartment
+    Both,
+    /// Neutral apartment
+    Neutral,
+}
+
+impl SerializableValue for ThreadingModel {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            ThreadingModel::Apartment => "Apartment",
+            ThreadingModel::Free => "Free",
+            ThreadingModel::Both => "Both",
+            ThreadingModel::Neutral => "Neutral",
+        })
+    }
+}
+
+impl DeserializableValue for ThreadingModel {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "apartment" => Some(ThreadingModel::Apartment),
+            "free" => Some(ThreadingModel::Free),
+            "both" => Some(ThreadingModel::Both),
+            "neutral" => Some(ThreadingModel::Neutral),
+            _ => None,
+        }
+    }
+}
+
+/// Version of type library, `major.minor`
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct TypeLibVersion {
+    #[allow(missing_docs)]
+    pub major: u16,
+    #[allow(missing_docs)]
+    pub minor: u16,
+}
+
+impl SerializableValue for TypeLibVersion {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Owned(format!("{}.{}", self.major, self.minor))
+    }
+}
+
+impl DeserializableValue for TypeLibVersion {
+    fn deserialize(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(2, '.');
+        Some(TypeLibVersion {
+            major: parts.next()?.parse().ok()?,
+            minor: parts.next()?.parse().ok()?,
+        })
+    }
+}
+
+/// Flag of type library
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TypeLibFlag {
+    #[allow(missing_docs)]
+    Control,
+    #[allow(missing_docs)]
+    Hidden,
+    #[allow(missing_docs)]
+    Restricted,
+    #[allow(missing_docs)]
+    HasDiskImage,
+}
+
+impl SerializableValue for TypeLibFlag {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            TypeLibFlag::Control => "control",
+            TypeLibFlag::Hidden => "hidden",
+            TypeLibFlag::Restricted => "restricted",
+            TypeLibFlag::HasDiskImage => "hasdiskimage",
+        })
+    }
+}
+
+impl DeserializableValue for TypeLibFlag {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "control" => Some(TypeLibFlag::Control),
+            "hidden" => Some(TypeLibFlag::Hidden),
+            "restricted" => Some(TypeLibFlag::Restricted),
+            "hasdiskimage" => Some(TypeLibFlag::HasDiskImage),
+            _ => None,
+        }
+    }
+}
+
+/// COM class implemented by the file (`comClass`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComClass {
+    #[allow(missing_docs)]
+    pub clsid: Guid,
+    #[allow(missing_docs)]
+    pub threading_model: Option<ThreadingModel>,
+    /// Version-dependent ProgID
+    pub progid: Option<String>,
+    /// Type library which describes the class
+    pub tlbid: Option<Guid>,
+    #[allow(missing_docs)]
+    pub description: Option<String>,
+    /// Additional ProgIDs, such as version-independent one
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progids: Vec<String>,
+    /// Content of `comClass` element which is not modeled, such as `miscStatus`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl ComClass {
+    const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "comClass",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ELEMENT_PROGID_NAME: XmlName<'static> = XmlName {
+        local_name: "progid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_CLSID_NAME: XmlName<'static> = XmlName {
+        local_name: "clsid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_THREADING_MODEL_NAME: XmlName<'static> = XmlName {
+        local_name: "threadingModel",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_PROGID_NAME: XmlName<'static> = XmlName {
+        local_name: "progid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_TLBID_NAME: XmlName<'static> = XmlName {
+        local_name: "tlbid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_DESCRIPTION_NAME: XmlName<'static> = XmlName {
+        local_name: "description",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new(clsid: Guid) -> Self {
+        ComClass {
+            clsid,
+            threading_model: None,
+            progid: None,
+            tlbid: None,
+            description: None,
+            progids: Vec::new(),
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+/// Check ProgIDs of COM class
+pub(crate) fn validate_progids(
+    progid: &Option<String>,
+    progids: &[String],
+    path: &Path<'_>,
+    problems: &mut Problems,
+) {
+    if matches!(progid, Some(progid) if progid.is_empty()) {
+        problems.error(
+            &path.appended("progid".into()),
+            "empty-progid",
+            "progid is empty",
+            "remove progid or set a ProgID such as `Vendor.Component.1`",
+        );
+    }
+    let progids_path = path.appended("progids".into());
+    for (idx, progid) in progids.iter().enumerate() {
+        if progid.is_empty() {
+            problems.error(
+                &progids_path.appended(idx.into()),
+                "empty-progid",
+                "progid is empty",
+                "remove the entry or set a ProgID such as `Vendor.Component`",
+            );
+        }
+    }
+}
+
+impl Validatable for ComClass {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        validate_progids(&self.progid, &self.progids, &path, problems);
+        if self.threading_model.is_none() {
+            problems.warning(
+                &path.appended("threading_model".into()),
+                "missing-threading-model",
+                "class without threading model is created in the main single-threaded apartment",
+                "set threading_model, usually `Apartment` or `Both`",
+            );
+        }
+    }
+}
+
+impl SerializableElement for ComClass {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let mut values = vec![(ComClass::ATTRIBUTE_CLSID_NAME, self.clsid.serialize())];
+        if let Some(threading_model) = &self.threading_model {
+            values.push((
+                ComClass::ATTRIBUTE_THREADING_MODEL_NAME,
+                threading_model.serialize(),
+            ));
+        }
+        if let Some(progid) = &self.progid {
+            values.push((ComClass::ATTRIBUTE_PROGID_NAME, Cow::Borrowed(progid)));
+        }
+        if let Some(tlbid) = &self.tlbid {
+            values.push((ComClass::ATTRIBUTE_TLBID_NAME, tlbid.serialize()));
+        }
+        if let Some(description) = &self.description {
+            values.push((
+                ComClass::ATTRIBUTE_DESCRIPTION_NAME,
+                Cow::Borrowed(description),
+            ));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: ComClass::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+
+        let unknown_path = path.appended("unknown".into());
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        for progid in &self.progids {
+            writer.write(XmlEvent::StartElement {
+                name: ComClass::ELEMENT_PROGID_NAME,
+                attributes: Cow::Borrowed(&[]),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::Characters(progid))?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(ComClass::ELEMENT_PROGID_NAME.local_name),
+        )?;
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for ComClass {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let progids_path = path.appended("progids".into());
+        Ok(ComClass {
+            clsid: element.required_value("clsid", &path.appended("clsid".into()))?,
+            threading_model: element
+                .value("threadingModel", &path.appended("threading_model".into()))?,
+            progid: element.value("progid", &path.appended("progid".into()))?,
+            tlbid: element.value("tlbid", &path.appended("tlbid".into()))?,
+            description: element.value("description", &path.appended("description".into()))?,
+            progids: element
+                .elements()
+                .filter(|child| child.is(&ComClass::ELEMENT_PROGID_NAME))
+                .enumerate()
+                .map(|(idx, progid)| progid.text_value(&progids_path.appended(idx.into())))
+                .collect::<ParseResult<_>>()?,
+            unknown: UnknownContent::capture(
+                element,
+                |child| child.is(&ComClass::ELEMENT_PROGID_NAME),
+                &[
+                    ComClass::ATTRIBUTE_CLSID_NAME.local_name,
+                    ComClass::ATTRIBUTE_THREADING_MODEL_NAME.local_name,
+                    ComClass::ATTRIBUTE_PROGID_NAME.local_name,
+                    ComClass::ATTRIBUTE_TLBID_NAME.local_name,
+                    ComClass::ATTRIBUTE_DESCRIPTION_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
+
+/// Proxy/stub of COM interface
+///
+/// It is written as `comInterfaceProxyStub` in [`File`](struct.File.html),
+/// or as `comInterfaceExternalProxyStub` in [`AssemblyManifest`](../struct.AssemblyManifest.html) for interfaces whose proxy/stub is outside of the assembly.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComInterfaceProxyStub {
+    #[allow(missing_docs)]
+    pub iid: Guid,
+    /// Name of interface
+    pub name: String,
+    /// Type library which describes the interface
+    pub tlbid: Option<Guid>,
+    /// Number of methods of the interface
+    pub num_methods: Option<u32>,
+    /// CLSID of proxy/stub, which is IID of the interface when not specified
+    pub proxy_stub_clsid32: Option<Guid>,
+    /// IID of the interface from which this interface is derived
+    pub base_interface: Option<Guid>,
+    /// Content of the element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl ComInterfaceProxyStub {
+    const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "comInterfaceProxyStub",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    pub(crate) const ELEMENT_EXTERNAL_NAME: XmlName<'static> = XmlName {
+        local_name: "comInterfaceExternalProxyStub",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_IID_NAME: XmlName<'static> = XmlName {
+        local_name: "iid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NAME_NAME: XmlName<'static> = XmlName {
+        local_name: "name",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_TLBID_NAME: XmlName<'static> = XmlName {
+        local_name: "tlbid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NUM_METHODS_NAME: XmlName<'static> = XmlName {
+        local_name: "numMethods",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_PROXY_STUB_CLSID32_NAME: XmlName<'static> = XmlName {
+        local_name: "proxyStubClsid32",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_BASE_INTERFACE_NAME: XmlName<'static> = XmlName {
+        local_name: "baseInterface",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    /// CLSID of the proxy/stub for `oleautomation` interfaces, which marshals with type library
+    pub const OLE_AUTOMATION_PROXY_STUB_CLSID: Guid =
+        Guid::from_u128(0x00020424_0000_0000_c000_000000000046);
+
+    #[allow(missing_docs)]
+    pub fn new<S: AsRef<str>>(iid: Guid, name: S) -> Self {
+        ComInterfaceProxyStub {
+            iid,
+            name: name.as_ref().to_string(),
+            tlbid: None,
+            num_methods: None,
+            proxy_stub_clsid32: None,
+            base_interface: None,
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for ComInterfaceProxyStub {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if self.name.is_empty() {
+            problems.error(
+                &path.appended("name".into()),
+                "empty-name",
+                "name of interface is required",
+                "set name of interface, such as `IExample`",
+            );
+        }
+        if self.base_interface == Some(self.iid) {
+            problems.error(
+                &path.appended("base_interface".into()),
+                "self-derived-interface",
+                "interface can't be derived from itself",
+                "set IID of base interface, or remove base_interface",
+            );
+        }
+        if matches!(self.num_methods, Some(num_methods) if num_methods < 3) {
+            problems.error(
+                &path.appended("num_methods".into()),
+                "num-methods",
+                "num_methods must include 3 methods of IUnknown",
+                "count all methods including inherited ones",
+            );
+        }
+    }
+}
+
+impl SerializableElement for ComInterfaceProxyStub {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        self.write_element(writer, path, ComInterfaceProxyStub::ELEMENT_NAME)
+    }
+}
+
+impl ComInterfaceProxyStub {
+    /// Serialize as `comInterfaceExternalProxyStub`
+    pub(crate) fn serialize_external(
+        &self,
+        writer: &mut XmlWriter<'_>,
+        path: Path<'_>,
+    ) -> SerializeResult<()> {
+        self.write_element(writer, path, ComInterfaceProxyStub::ELEMENT_EXTERNAL_NAME)
+    }
+
+    fn write_element(
+        &self,
+        writer: &mut XmlWriter<'_>,
+        path: Path<'_>,
+        name: XmlName<'static>,
+    ) -> SerializeResult<()> {
+        let mut values = vec![
+            (
+                ComInterfaceProxyStub::ATTRIBUTE_IID_NAME,
+                self.iid.serialize(),
+            ),
+            (
+                ComInterfaceProxyStub::ATTRIBUTE_NAME_NAME,
+                Cow::Borrowed(self.name.as_str()),
+            ),
+        ];
+        if let Some(tlbid) = &self.tlbid {
+            values.push((
+                ComInterfaceProxyStub::ATTRIBUTE_TLBID_NAME,
+                tlbid.serialize(),
+            ));
+        }
+        if let Some(num_methods) = &self.num_methods {
+            values.push((
+                ComInterfaceProxyStub::ATTRIBUTE_NUM_METHODS_NAME,
+                num_methods.serialize(),
+            ));
+        }
+        if let Some(proxy_stub_clsid32) = &self.proxy_stub_clsid32 {
+            values.push((
+                ComInterfaceProxyStub::ATTRIBUTE_PROXY_STUB_CLSID32_NAME,
+                proxy_stub_clsid32.serialize(),
+            ));
+        }
+        if let Some(base_interface) = &self.base_interface {
+            values.push((
+                ComInterfaceProxyStub::ATTRIBUTE_BASE_INTERFACE_NAME,
+                base_interface.serialize(),
+            ));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown
+            .serialize_nodes(writer, &path.appended("unknown".into()), None)?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for ComInterfaceProxyStub {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        Ok(ComInterfaceProxyStub {
+            iid: element.required_value("iid", &path.appended("iid".into()))?,
+            name: element.required_value("name", &path.appended("name".into()))?,
+            tlbid: element.value("tlbid", &path.appended("tlbid".into()))?,
+            num_methods: element.value("numMethods", &path.appended("num_methods".into()))?,
+            proxy_stub_clsid32: element.value(
+                "proxyStubClsid32",
+                &path.appended("proxy_stub_clsid32".into()),
+            )?,
+            base_interface: element
+                .value("baseInterface", &path.appended("base_interface".into()))?,
+            unknown: UnknownContent::capture(
+                element,
+                |_| false,
+                &[
+                    ComInterfaceProxyStub::ATTRIBUTE_IID_NAME.local_name,
+                    ComInterfaceProxyStub::ATTRIBUTE_NAME_NAME.local_name,
+                    ComInterfaceProxyStub::ATTRIBUTE_TLBID_NAME.local_name,
+                    ComInterfaceProxyStub::ATTRIBUTE_NUM_METHODS_NAME.local_name,
+                    ComInterfaceProxyStub::ATTRIBUTE_PROXY_STUB_CLSID32_NAME.local_name,
+                    ComInterfaceProxyStub::ATTRIBUTE_BASE_INTERFACE_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
+
+/// Type library contained in the file (`typelib`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeLib {
+    #[allow(missing_docs)]
+    pub tlbid: Guid,
+    #[allow(missing_docs)]
+    pub version: TypeLibVersion,
+    /// Directory of help files. It can be empty.
+    pub helpdir: String,
+    /// Hexadecimal LCID of the type library
+    pub resourceid: Option<String>,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flags: Vec<TypeLibFlag>,
+    /// Content of `typelib` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl TypeLib {
+    const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "typelib",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_TLBID_NAME: XmlName<'static> = XmlName {
+        local_name: "tlbid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_VERSION_NAME: XmlName<'static> = XmlName {
+        local_name: "version",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_HELPDIR_NAME: XmlName<'static> = XmlName {
+        local_name: "helpdir",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_RESOURCEID_NAME: XmlName<'static> = XmlName {
+        local_name: "resourceid",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_FLAGS_NAME: XmlName<'static> = XmlName {
+        local_name: "flags",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new(tlbid: Guid, version: TypeLibVersion) -> Self {
+        TypeLib {
+            tlbid,
+            version,
+            helpdir: String::new(),
+            resourceid: None,
+            flags: Vec::new(),
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for TypeLib {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if let Some(resourceid) = &self.resourceid {
+            if resourceid.is_empty() || !resourceid.chars().all(|c| c.is_ascii_hexdigit()) {
+                problems.error(
+                    &path.appended("resourceid".into()),
+                    "resourceid",
+                    format!("resourceid must be hexadecimal LCID - {}", resourceid),
+                    "use hexadecimal LCID such as `0` or `409`",
+                );
+            }
+        }
+    }
+}
+
+impl SerializableElement for TypeLib {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let mut values = vec![
+            (TypeLib::ATTRIBUTE_TLBID_NAME, self.tlbid.serialize()),
+            (TypeLib::ATTRIBUTE_VERSION_NAME, self.version.serialize()),
+            (
+                TypeLib::ATTRIBUTE_HELPDIR_NAME,
+                Cow::Borrowed(self.helpdir.as_str()),
+            ),
+        ];
+        if let Some(resourceid) = &self.resourceid {
+            values.push((
+                TypeLib::ATTRIBUTE_RESOURCEID_NAME,
+                Cow::Borrowed(resourceid),
+            ));
+        }
+        if !self.flags.is_empty() {
+            values.push((
+                TypeLib::ATTRIBUTE_FLAGS_NAME,
+                Cow::Owned(
+                    self.flags
+                        .iter()
+                        .map(|flag| flag.serialize())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+            ));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: TypeLib::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown
+            .serialize_nodes(writer, &path.appended("unknown".into()), None)?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for TypeLib {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let flags_path = path.appended("flags".into());
+        Ok(TypeLib {
+            tlbid: element.required_value("tlbid", &path.appended("tlbid".into()))?,
+            version: element.required_value("version", &path.appended("version".into()))?,
+            helpdir: element.required_value("helpdir", &path.appended("helpdir".into()))?,
+            resourceid: element.value("resourceid", &path.appended("resourceid".into()))?,
+            flags: match element.attribute("flags") {
+                Some(flags) => flags
+                    .split(',')
+                    .enumerate()
+                    .map(|(idx, flag)| {
+                        TypeLibFlag::deserialize(flag.trim()).ok_or_else(|| {
+                            element.invalid(
+                                &flags_path.appended(idx.into()),
+                                format!("unexpected value of flags - {}", flag),
+                            )
+                        })
+                    })
+                    .collect::<ParseResult<_>>()?,
+                None => Vec::new(),
+            },
+            unknown: UnknownContent::capture(
+                element,
+                |_| false,
+                &[
+                    TypeLib::ATTRIBUTE_TLBID_NAME.local_name,
+                    TypeLib::ATTRIBUTE_VERSION_NAME.local_name,
+                    TypeLib::ATTRIBUTE_HELPDIR_NAME.local_name,
+                    TypeLib::ATTRIBUTE_RESOURCEID_NAME.local_name,
+                    TypeLib::ATTRIBUTE_FLAGS_NAME.local_name,
+                ],
+            ),
+        })
+    }
+}
+
+/// Window class registered by the file (`windowClass`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowClass {
+    /// Name of window class
+    pub name: String,
+    /// Whether the name is prefixed with assembly version. Default is `true`
+    pub versioned: Option<bool>,
+    /// Content of `windowClass` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl WindowClass {
+    const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "windowClass",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_VERSIONED_NAME: XmlName<'static> = XmlName {
+        local_name: "versioned",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        WindowClass {
+            name: name.as_ref().to_string(),
+            versioned: None,
+            unknown: UnknownContent::default(),
+        }
+    }
+}
+
+impl Validatable for WindowClass {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if self.name.is_empty() {
+            problems.error(
+                &path.appended("name".into()),
+                "empty-name",
+                "name of window class is required",
+                "set name of window class",
+            );
+        }
+    }
+}
+
+impl SerializableElement for WindowClass {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let versioned = self.versioned.map(YesNo);
+        let versioned = versioned.as_ref().map(|versioned| versioned.serialize());
+        let mut attributes = Vec::<XmlAttribute>::new();
+        if let Some(versioned) = &versioned {
+            attributes.push(XmlAttribute {
+                name: WindowClass::ATTRIBUTE_VERSIONED_NAME,
+                value: versioned,
+            });
+        }
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: WindowClass::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+        self.unknown
+            .serialize_nodes(writer, &path.appended("unknown".into()), None)?;
+        writer.write(XmlEvent::Characters(&self.name))?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for WindowClass {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        Ok(WindowClass {
+            name: element.text_value(&path.appended("name".into()))?,
+            versioned: element
+                .value::<YesNo>("versioned", &path.appended("versioned".into()))?
+                .map(|versioned| versioned.0),
+            unknown: UnknownContent::capture(
+                element,
+                |_| false,
+                &[WindowClass::ATTRIBUTE_VERSIONED_NAME.local_name],
+            ),
+        })
+    }
+}
+
+/// File of assembly (`file`)
+///
+/// It describes registration-free COM components implemented by the file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct File {
+    /// Name of file, relative to the manifest
+    pub name: String,
+    /// Hash of file
+    pub hash: Option<Vec<u8>>,
+    /// Algorithm of `hash`. SHA1 is used when not specified
+    pub hash_algorithm: Option<HashAlgorithm>,
+    /// Size of file in bytes
+    pub size: Option<u64>,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub com_classes: Vec<ComClass>,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub com_interface_proxy_stubs: Vec<ComInterfaceProxyStub>,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub typelibs: Vec<TypeLib>,
+    #[allow(missing_docs)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub window_classes: Vec<WindowClass>,
+    /// Content of `file` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl File {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "file",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_NAME_NAME: XmlName<'static> = XmlName {
+        local_name: "name",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_HASH_NAME: XmlName<'static> = XmlName {
+        local_name: "hash",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_HASHALG_NAME: XmlName<'static> = XmlName {
+        local_name: "hashalg",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ATTRIBUTE_SIZE_NAME: XmlName<'static> = XmlName {
+        local_name: "size",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+
+    #[allow(missing_docs)]
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        File {
+            name: name.as_ref().to_string(),
+            hash: None,
+            hash_algorithm: None,
+            size: None,
+            com_classes: Vec::new(),
+            com_interface_proxy_stubs: Vec::new(),
+            typelibs: Vec::new(),
+            window_classes: Vec::new(),
+            unknown: UnknownContent::default(),
+        }
+    }
+
+    fn is_known_element(element: &Element) -> bool {
+        element.is(&ComClass::ELEMENT_NAME)
+            || element.is(&ComInterfaceProxyStub::ELEMENT_NAME)
+            || element.is(&TypeLib::ELEMENT_NAME)
+            || element.is(&WindowClass::ELEMENT_NAME)
+    }
+}
+
+impl Validatable for File {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if self.name.is_empty() {
+            problems.error(
+                &path.appended("name".into()),
+                "empty-name",
+                "name of file is required",
+                "set path of file relative to the manifest",
+            );
+        }
+        if let Some(hash) = &self.hash {
+            let hash_algorithm = self.hash_algorithm.unwrap_or(HashAlgorithm::Sha1);
+            if hash.len() != hash_algorithm.digest_len() {
+                problems.error(
+                    &path.appended("hash".into()),
+                    "hash-length",
+                    format!(
+                        "{} hash must be {} bytes, but {} bytes",
+                        hash_algorithm.serialize(),
+                        hash_algorithm.digest_len(),
+                        hash.len()
+                    ),
+                    "set hash_algorithm which matches the hash",
+                );
+            }
+        } else if self.hash_algorithm.is_some() {
+            problems.error(
+                &path.appended("hash_algorithm".into()),
+                "hash-algorithm-without-hash",
+                "hash_algorithm requires hash",
+                "set hash, or remove hash_algorithm",
+            );
+        }
+
+        self.com_classes
+            .validate(path.appended("com_classes".into()), problems);
+        self.com_interface_proxy_stubs
+            .validate(path.appended("com_interface_proxy_stubs".into()), problems);
+        self.typelibs
+            .validate(path.appended("typelibs".into()), problems);
+        self.window_classes
+            .validate(path.appended("window_classes".into()), problems);
+    }
+}
+
+impl SerializableElement for File {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        let mut values = vec![(File::ATTRIBUTE_NAME_NAME, Cow::Borrowed(self.name.as_str()))];
+        if let Some(hash) = &self.hash {
+            values.push((File::ATTRIBUTE_HASH_NAME, Cow::Owned(to_hex(hash))));
+        }
+        if let Some(hash_algorithm) = &self.hash_algorithm {
+            values.push((File::ATTRIBUTE_HASHALG_NAME, hash_algorithm.serialize()));
+        }
+        if let Some(size) = &self.size {
+            values.push((File::ATTRIBUTE_SIZE_NAME, size.serialize()));
+        }
+        let mut attributes = values
+            .iter()
+            .map(|(name, value)| XmlAttribute { name: *name, value })
+            .collect::<Vec<_>>();
+        self.unknown.extend_attributes(&mut attributes);
+
+        writer.write(XmlEvent::StartElement {
+            name: File::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace(XmlNamespace::empty())),
+        })?;
+
+        let unknown_path = path.appended("unknown".into());
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        let com_classes_path = path.appended("com_classes".into());
+        for (idx, com_class) in self.com_classes.iter().enumerate() {
+            com_class.serialize(writer, com_classes_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(ComClass::ELEMENT_NAME.local_name),
+        )?;
+
+        let com_interface_proxy_stubs_path = path.appended("com_interface_proxy_stubs".into());
+        for (idx, proxy_stub) in self.com_interface_proxy_stubs.iter().enumerate() {
+            proxy_stub.serialize(writer, com_interface_proxy_stubs_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(ComInterfaceProxyStub::ELEMENT_NAME.local_name),
+        )?;
+
+        let typelibs_path = path.appended("typelibs".into());
+        for (idx, typelib) in self.typelibs.iter().enumerate() {
+            typelib.serialize(writer, typelibs_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(TypeLib::ELEMENT_NAME.local_name),
+        )?;
+
+        let window_classes_path = path.appended("window_classes".into());
+        for (idx, window_class) in self.window_classes.iter().enumerate() {
+            window_class.serialize(writer, window_classes_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(WindowClass::ELEMENT_NAME.local_name),
+        )?;
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for File {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let mut file = File {
+            name: element.required_value("name", &path.appended("name".into()))?,
+            hash: element
+                .attribute("hash")
+                .map(|hash| {
+                    from_hex(hash).ok_or_else(|| {
+                        element.invalid(
+                            &path.appended("hash".into()),
+                            format!("unexpected value of hash - {}", hash),
+                        )
+                    })
+                })
+                .transpose()?,
+            hash_algorithm: element.value("hashalg", &path.appended("hash_algorithm".into()))?,
+            size: element.value("size", &path.appended("size".into()))?,
+            unknown: UnknownContent::capture(
+                element,
+                File::is_known_element,
+                &[
+                    File::ATTRIBUTE_NAME_NAME.local_name,
+                    File::ATTRIBUTE_HASH_NAME.local_name,
+                    File::ATTRIBUTE_HASHALG_NAME.local_name,
+                    File::ATTRIBUTE_SIZE_NAME.local_name,
+                ],
+            ),
+            ..File::new("")
+        };
+
+        let com_classes_path = path.appended("com_classes".into());
+        let com_interface_proxy_stubs_path = path.appended("com_interface_proxy_stubs".into());
+        let typelibs_path = path.appended("typelibs".into());
+        let window_classes_path = path.appended("window_classes".into());
+        for child in element.elements() {
+            if child.is(&ComClass::ELEMENT_NAME) {
+                let path = com_classes_path.appended(file.com_classes.len().into());
+                file.com_classes.push(ComClass::deserialize(child, path)?);
+            } else if child.is(&ComInterfaceProxyStub::ELEMENT_NAME) {
+                let path = com_interface_proxy_stubs_path
+                    .appended(file.com_interface_proxy_stubs.len().into());
+                file.com_interface_proxy_stubs
+                    .push(ComInterfaceProxyStub::deserialize(child, path)?);
+            } else if child.is(&TypeLib::ELEMENT_NAME) {
+                let path = typelibs_path.appended(file.typelibs.len().into());
+                file.typelibs.push(TypeLib::deserialize(child, path)?);
+            } else if child.is(&WindowClass::ELEMENT_NAME) {
+                let path = window_classes_path.appended(file.window_classes.len().into());
+                file.window_classes
+                    .push(WindowClass::deserialize(child, path)?);
+            }
+        }
+
+        Ok(file)
+    }
+}
@@ -0,0 +1,202 @@
+use super::common::PublicKeyToken;
+
+/// Error of deriving [`PublicKeyToken`](struct.PublicKeyToken.html) from key material
+#[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
+pub enum PublicKeyError {
+    #[allow(missing_docs)]
+    #[error("key data is truncated or malformed")]
+    Malformed,
+    #[allow(missing_docs)]
+    #[error("only RSA keys are supported")]
+    UnsupportedAlgorithm,
+}
+
+const PUBLICKEYBLOB: u8 = 0x06;
+const PRIVATEKEYBLOB: u8 = 0x07;
+const CUR_BLOB_VERSION: u8 = 0x02;
+const CALG_RSA_SIGN: u32 = 0x2400;
+const CALG_SHA1: u32 = 0x8004;
+const MAGIC_RSA1: u32 = 0x3141_5352;
+const MAGIC_RSA2: u32 = 0x3241_5352;
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// RSA public key, modulus is little-endian as CryptoAPI stores
+struct RsaPublicKey {
+    exponent: u32,
+    modulus: Vec<u8>,
+}
+
+impl RsaPublicKey {
+    /// Read CryptoAPI `PUBLICKEYBLOB` or `PRIVATEKEYBLOB`
+    fn from_capi_blob(blob: &[u8]) -> Result<Self, PublicKeyError> {
+        let u32_at = |offset: usize| {
+            blob.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                .ok_or(PublicKeyError::Malformed)
+        };
+        let expected_magic = match blob.first() {
+            Some(&PUBLICKEYBLOB) => MAGIC_RSA1,
+            Some(&PRIVATEKEYBLOB) => MAGIC_RSA2,
+            _ => return Err(PublicKeyError::Malformed),
+        };
+        if u32_at(8)? != expected_magic {
+            return Err(PublicKeyError::UnsupportedAlgorithm);
+        }
+        let bit_len = u32_at(12)? as usize;
+        let modulus = blob
+            .get(20..20 + bit_len / 8)
+            .ok_or(PublicKeyError::Malformed)?;
+
+        Ok(RsaPublicKey {
+            exponent: u32_at(16)?,
+            modulus: modulus.to_vec(),
+        })
+    }
+
+    /// Read `SubjectPublicKeyInfo` of X.509 DER certificate
+    fn from_x509_der(der: &[u8]) -> Result<Self, PublicKeyError> {
+        let (certificate, _) = der_expect(der, 0x30)?;
+        let (tbs_certificate, _) = der_expect(certificate, 0x30)?;
+
+        let mut rest = tbs_certificate;
+        if rest.first() == Some(&0xa0) {
+            rest = der_read(rest)?.2;
+        }
+        // serialNumber, signature, issuer, validity, subject
+        for tag in &[0x02, 0x30, 0x30, 0x30, 0x30] {
+            rest = der_expect(rest, *tag)?.1;
+        }
+        let (subject_public_key_info, _) = der_expect(rest, 0x30)?;
+
+        let (algorithm, rest) = der_expect(subject_public_key_info, 0x30)?;
+        let (oid, _) = der_expect(algorithm, 0x06)?;
+        if oid != OID_RSA_ENCRYPTION {
+            return Err(PublicKeyError::UnsupportedAlgorithm);
+        }
+        let (public_key, _) = der_expect(rest, 0x03)?;
+        // First byte of BIT STRING is number of unused bits
+        let public_key = match public_key.split_first() {
+            Some((0, public_key)) => public_key,
+            _ => return Err(PublicKeyError::Malformed),
+        };
+        let (rsa_public_key, _) = der_expect(public_key, 0x30)?;
+        let (modulus, rest) = der_expect(rsa_public_key, 0x02)?;
+        let (exponent, _) = der_expect(rest, 0x02)?;
+
+        let exponent = der_unsigned(exponent);
+        if exponent.len() > 4 {
+            return Err(PublicKeyError::UnsupportedAlgorithm);
+        }
+
+        Ok(RsaPublicKey {
+            exponent: exponent
+                .iter()
+                .fold(0u32, |value, byte| (value << 8) | *byte as u32),
+            modulus: der_unsigned(modulus).iter().rev().copied().collect(),
+        })
+    }
+
+    /// Strong name public key blob, which is CryptoAPI `PUBLICKEYBLOB` with signature and hash algorithm header
+    fn to_strong_name_blob(&self) -> Vec<u8> {
+        let mut capi_blob = vec![PUBLICKEYBLOB, CUR_BLOB_VERSION, 0, 0];
+        capi_blob.extend_from_slice(&CALG_RSA_SIGN.to_le_bytes());
+        capi_blob.extend_from_slice(&MAGIC_RSA1.to_le_bytes());
+        capi_blob.extend_from_slice(&((self.modulus.len() * 8) as u32).to_le_bytes());
+        capi_blob.extend_from_slice(&self.exponent.to_le_bytes());
+        capi_blob.extend_from_slice(&self.modulus);
+
+        let mut blob = Vec::with_capacity(12 + capi_blob.len());
+        blob.extend_from_slice(&CALG_RSA_SIGN.to_le_bytes());
+        blob.extend_from_slice(&CALG_SHA1.to_le_bytes());
+        blob.extend_from_slice(&(capi_blob.len() as u32).to_le_bytes());
+        blob.extend_from_slice(&capi_blob);
+        blob
+    }
+}
+
+/// Read DER TLV, returns tag, content and rest
+fn der_read(data: &[u8]) -> Result<(u8, &[u8], &[u8]), PublicKeyError> {
+    let (&tag, data) = data.split_first().ok_or(PublicKeyError::Malformed)?;
+    let (&len, mut data) = data.split_first().ok_or(PublicKeyError::Malformed)?;
+    let len = if len & 0x80 == 0 {
+        len as usize
+    } else {
+        let count = (len & 0x7f) as usize;
+        if count == 0 || count > 4 || data.len() < count {
+            return Err(PublicKeyError::Malformed);
+        }
+        let (len_bytes, rest) = data.split_at(count);
+        data = rest;
+        len_bytes
+            .iter()
+            .fold(0usize, |value, byte| (value << 8) | *byte as usize)
+    };
+    if data.len() < len {
+        return Err(PublicKeyError::Malformed);
+    }
+    let (content, rest) = data.split_at(len);
+
+    Ok((tag, content, rest))
+}
+
+fn der_expect(data: &[u8], expected_tag: u8) -> Result<(&[u8], &[u8]), PublicKeyError> {
+    match der_read(data)? {
+        (tag, content, rest) if tag == expected_tag => Ok((content, rest)),
+        _ => Err(PublicKeyError::Malformed),
+    }
+}
+
+/// Strip sign padding of DER INTEGER
+fn der_unsigned(integer: &[u8]) -> &[u8] {
+    match integer.split_first() {
+        Some((0, rest)) if !rest.is_empty() => rest,
+        _ => integer,
+    }
+}
+
+/// Derivation from key material
+///
+/// Token is the last 8 bytes of SHA-1 hash of strong name public key blob, in reversed order.
+impl PublicKeyToken {
+    /// Token of strong name public key blob, such as output of `sn -p`
+    pub fn from_public_key_blob(blob: &[u8]) -> Self {
+        let digest = sha1_smol::Sha1::from(blob).digest().bytes();
+        let mut token = [0u8; 8];
+        token.copy_from_slice(&digest[12..]);
+        token.reverse();
+
+        PublicKeyToken(token)
+    }
+
+    /// Token of strong name key file.
+    ///
+    /// Both key pair (`sn -k`) and public key only (`sn -p`) files are accepted.
+    pub fn from_snk(snk: &[u8]) -> Result<Self, PublicKeyError> {
+        match snk.first() {
+            Some(&PUBLICKEYBLOB) | Some(&PRIVATEKEYBLOB) => {
+                let key = RsaPublicKey::from_capi_blob(snk)?;
+                Ok(PublicKeyToken::from_public_key_blob(
+                    &key.to_strong_name_blob(),
+                ))
+            }
+            _ => {
+                let len = snk
+                    .get(8..12)
+                    .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                    .ok_or(PublicKeyError::Malformed)?;
+                if snk.len() != 12 + len as usize {
+                    return Err(PublicKeyError::Malformed);
+                }
+                Ok(PublicKeyToken::from_public_key_blob(snk))
+            }
+        }
+    }
+
+    /// Token of RSA public key of X.509 DER certificate
+    pub fn from_x509_der(der: &[u8]) -> Result<Self, PublicKeyError> {
+        let key = RsaPublicKey::from_x509_der(der)?;
+        Ok(PublicKeyToken::from_public_key_blob(
+            &key.to_strong_name_blob(),
+        ))
+    }
+}
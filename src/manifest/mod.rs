@@ -2,18 +2,41 @@ use crate::debug::Path;
 use crate::xml_alias::{namespace, XmlAttribute, XmlName, XmlNamespace, XmlVersion};
 use crate::{
     consts::NS_MS_ASM_V1,
-    serialize::{SerializableElement, SerializableValue, SerializeResult},
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{
+        SerializableElement, SerializableValue, SerializeError, SerializeResult, XmlWriter,
+    },
+    validate::{Problem, Problems, Severity, Validatable},
 };
 use std::borrow::Cow;
-use std::io::Write;
-use xml::writer::{EmitterConfig, XmlEvent};
+use std::io::{Read, Write};
+use xml::{
+    name::OwnedName,
+    writer::{EmitterConfig, XmlEvent},
+};
 
+mod clr;
+pub use clr::*;
 mod compatibility;
 pub use compatibility::*;
 mod common;
 pub use common::*;
 mod dependency;
 pub use dependency::*;
+mod extension;
+pub use extension::*;
+mod file;
+pub use file::*;
+mod key;
+pub use key::*;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod trust_info;
+pub use trust_info::*;
+mod unknown;
+pub use unknown::*;
+mod windows_settings;
+pub use windows_settings::*;
 
 /// Version of manifest
 ///
@@ -32,33 +55,116 @@ impl SerializableValue for ManifestVersion {
     }
 }
 
+impl DeserializableValue for ManifestVersion {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "1.0" => Some(ManifestVersion::V1_0),
+            _ => None,
+        }
+    }
+}
+
 /// Assembly manifest
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct AssemblyManifest {
     /// Version of manifest
     pub manifest_version: ManifestVersion,
+    /// Identity of this assembly.
+    ///
+    /// Required for DLL and shared assembly manifests, and optional for application manifests.
+    pub assembly_identity: Option<AssemblyIdentity>,
+    /// Whether this manifest is not inherited by activation contexts of its dependents.
+    ///
+    /// Requires `assembly_identity`.
+    pub no_inheritable: bool,
+    /// Description of this assembly
+    pub description: Option<String>,
+    /// Whether activation contexts created from this manifest are not inherited by new threads and windows
+    pub no_inherit: bool,
+    /// UAC settings
+    pub trust_info: TrustInfo,
     /// Compatibility info
     pub compatibility: Compatibility,
-    /// Can specify SxS dependencies
-    pub dependency: Dependency,
+    /// Windows settings of application
+    pub windows_settings: WindowsSettings,
+    /// Interfaces used by the application whose proxy/stub is outside of the assembly
+    pub com_interface_external_proxy_stubs: Vec<ComInterfaceProxyStub>,
+    /// SxS dependencies, one for each `dependency` element
+    pub dependencies: Vec<Dependency>,
+    /// Files of assembly, such as registration-free COM servers
+    pub files: Vec<File>,
+    /// Managed classes exposed to COM
+    pub clr_classes: Vec<ClrClass>,
+    /// Managed classes activated in surrogate process
+    pub clr_surrogates: Vec<ClrSurrogate>,
+    /// Content of `assembly` element which is not modeled
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+    /// Custom elements and XML fragments, which are written at the end of `assembly`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub extensions: Vec<Extension>,
+    /// Comments outside of `assembly` element.
+    ///
+    /// Comments following `assembly` are anchored after it.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub document_nodes: Vec<AnchoredNode>,
 }
 
 impl Default for AssemblyManifest {
     fn default() -> Self {
         AssemblyManifest {
             manifest_version: ManifestVersion::V1_0,
+            assembly_identity: None,
+            no_inheritable: false,
+            description: None,
+            no_inherit: false,
+            trust_info: TrustInfo::default(),
             compatibility: Compatibility::default(),
-            dependency: Dependency::default(),
+            windows_settings: WindowsSettings::default(),
+            com_interface_external_proxy_stubs: Vec::new(),
+            dependencies: Vec::new(),
+            files: Vec::new(),
+            clr_classes: Vec::new(),
+            clr_surrogates: Vec::new(),
+            unknown: UnknownContent::default(),
+            extensions: Vec::new(),
+            document_nodes: Vec::new(),
         }
     }
 }
 
+// `AssemblyManifest` must stay shareable between threads, including its extensions
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<AssemblyManifest>();
+};
+
 impl AssemblyManifest {
     const ELEMENT_NAME: XmlName<'static> = XmlName {
         local_name: "assembly",
         namespace: Some(NS_MS_ASM_V1),
         prefix: None,
     };
+    const ELEMENT_NO_INHERITABLE_NAME: XmlName<'static> = XmlName {
+        local_name: "noInheritable",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ELEMENT_DESCRIPTION_NAME: XmlName<'static> = XmlName {
+        local_name: "description",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
+    const ELEMENT_NO_INHERIT_NAME: XmlName<'static> = XmlName {
+        local_name: "noInherit",
+        namespace: Some(NS_MS_ASM_V1),
+        prefix: None,
+    };
     const ATTRIBUTE_MANIFEST_VERSION_NAME: XmlName<'static> = XmlName {
         local_name: "manifestVersion",
         namespace: Some(NS_MS_ASM_V1),
@@ -66,6 +172,82 @@ impl AssemblyManifest {
     };
 }
 
+/// Validation of whole manifest
+impl AssemblyManifest {
+    /// Check all values of manifest, and return every error and warning found.
+    ///
+    /// [`serialize_with_config`](#method.serialize_with_config) fails if any error is returned.
+    pub fn validate(&self) -> Vec<Problem> {
+        let mut problems = Problems::default();
+        self.validate_combination(&mut problems);
+
+        if let Some(identity) = &self.assembly_identity {
+            identity.validate(Path::new("assembly_identity".into()), &mut problems);
+        }
+        self.trust_info
+            .validate(Path::new("trust_info".into()), &mut problems);
+        self.compatibility
+            .validate(Path::new("compatibility".into()), &mut problems);
+        self.windows_settings
+            .validate(Path::new("windows_settings".into()), &mut problems);
+        self.com_interface_external_proxy_stubs.validate(
+            Path::new("com_interface_external_proxy_stubs".into()),
+            &mut problems,
+        );
+        self.dependencies
+            .validate(Path::new("dependencies".into()), &mut problems);
+        self.files
+            .validate(Path::new("files".into()), &mut problems);
+        self.clr_classes
+            .validate(Path::new("clr_classes".into()), &mut problems);
+        self.clr_surrogates
+            .validate(Path::new("clr_surrogates".into()), &mut problems);
+        self.extensions
+            .validate(Path::new("extensions".into()), &mut problems);
+
+        problems.into_vec()
+    }
+
+    /// Checks settings which depend on each other
+    fn validate_combination(&self, problems: &mut Problems) {
+        let identity_path = Path::new("assembly_identity".into());
+        match &self.assembly_identity {
+            Some(identity) if identity.version.is_none() => {
+                problems.error(
+                    &identity_path.appended("version".into()),
+                    "identity-without-version",
+                    "identity of the manifest requires version",
+                    "set version, such as `AssemblyVersion::from_cargo_env()`",
+                );
+            }
+            None if self.no_inheritable => {
+                problems.error(
+                    &Path::new("no_inheritable".into()),
+                    "no-inheritable-without-identity",
+                    "no_inheritable requires assembly_identity",
+                    "set assembly_identity, or remove no_inheritable",
+                );
+            }
+            _ => {}
+        }
+        if self.windows_settings.auto_elevate == Some(true) {
+            let level = self
+                .trust_info
+                .requested_execution_level
+                .map(|requested_execution_level| requested_execution_level.level);
+            if level != Some(ExecutionLevel::RequireAdministrator) {
+                let windows_settings_path = Path::new("windows_settings".into());
+                problems.error(
+                    &windows_settings_path.appended("auto_elevate".into()),
+                    "auto-elevate-without-admin",
+                    "auto_elevate requires requireAdministrator execution level",
+                    "set requested_execution_level to ExecutionLevel::RequireAdministrator",
+                );
+            }
+        }
+    }
+}
+
 /// Implementation of common serialization
 impl AssemblyManifest {
     /// Serialize manifest with custom config & writer
@@ -74,32 +256,311 @@ impl AssemblyManifest {
         config: EmitterConfig,
         writer: W,
     ) -> SerializeResult<W> {
-        let mut writer = config.create_writer(writer);
+        let problems = self
+            .validate()
+            .into_iter()
+            .filter(|problem| problem.severity == Severity::Error)
+            .collect::<Vec<_>>();
+        if !problems.is_empty() {
+            return Err(SerializeError::Invalid { problems });
+        }
+
+        let mut output = writer;
+        let mut writer = config.create_writer(&mut output as &mut dyn Write);
         writer.write(XmlEvent::StartDocument {
             version: XmlVersion::Version10,
             encoding: Some("UTF-8"),
             standalone: Some(true),
         })?;
+        let document_nodes_path = Path::new("document_nodes".into());
+        self.serialize_document_nodes(&mut writer, &document_nodes_path, None)?;
+        let manifest_version = self.manifest_version.serialize();
+        let mut attributes = vec![XmlAttribute {
+            name: AssemblyManifest::ATTRIBUTE_MANIFEST_VERSION_NAME,
+            value: &manifest_version,
+        }];
+        self.unknown.extend_attributes(&mut attributes);
         writer.write(XmlEvent::StartElement {
             name: AssemblyManifest::ELEMENT_NAME,
-            attributes: Cow::Borrowed(&[XmlAttribute {
-                name: AssemblyManifest::ATTRIBUTE_MANIFEST_VERSION_NAME,
-                value: &self.manifest_version.serialize(),
-            }]),
-            namespace: Cow::Owned({
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace({
                 let mut ns = XmlNamespace::empty();
                 ns.put(namespace::NS_NO_PREFIX.to_string(), NS_MS_ASM_V1);
                 ns
-            }),
+            })),
         })?;
 
+        let unknown_path = Path::new("unknown".into());
+        self.unknown
+            .serialize_nodes(&mut writer, &unknown_path, None)?;
+        // assemblyIdentity must be the first element, followed by noInheritable
+        if let Some(identity) = &self.assembly_identity {
+            identity.serialize(&mut writer, Path::new("assembly_identity".into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(AssemblyIdentity::ELEMENT_NAME.local_name),
+        )?;
+        if self.no_inheritable {
+            writer.write(XmlEvent::StartElement {
+                name: AssemblyManifest::ELEMENT_NO_INHERITABLE_NAME,
+                attributes: Cow::Borrowed(&[]),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(AssemblyManifest::ELEMENT_NO_INHERITABLE_NAME.local_name),
+        )?;
+        if let Some(description) = &self.description {
+            writer.write(XmlEvent::StartElement {
+                name: AssemblyManifest::ELEMENT_DESCRIPTION_NAME,
+                attributes: Cow::Borrowed(&[]),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::Characters(description))?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(AssemblyManifest::ELEMENT_DESCRIPTION_NAME.local_name),
+        )?;
+        if self.no_inherit {
+            writer.write(XmlEvent::StartElement {
+                name: AssemblyManifest::ELEMENT_NO_INHERIT_NAME,
+                attributes: Cow::Borrowed(&[]),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(AssemblyManifest::ELEMENT_NO_INHERIT_NAME.local_name),
+        )?;
+        self.trust_info
+            .serialize(&mut writer, Path::new("trust_info".into()))?;
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(TrustInfo::ELEMENT_NAME.local_name),
+        )?;
         self.compatibility
             .serialize(&mut writer, Path::new("compatibility".into()))?;
-        self.dependency
-            .serialize(&mut writer, Path::new("dependency".into()))?;
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(Compatibility::ELEMENT_NAME.local_name),
+        )?;
+        self.windows_settings
+            .serialize(&mut writer, Path::new("windows_settings".into()))?;
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(WindowsSettings::ELEMENT_APPLICATION_NAME.local_name),
+        )?;
+        let proxy_stubs_path = Path::new("com_interface_external_proxy_stubs".into());
+        for (idx, proxy_stub) in self.com_interface_external_proxy_stubs.iter().enumerate() {
+            proxy_stub.serialize_external(&mut writer, proxy_stubs_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(ComInterfaceProxyStub::ELEMENT_EXTERNAL_NAME.local_name),
+        )?;
+        let dependencies_path = Path::new("dependencies".into());
+        for (idx, dependency) in self.dependencies.iter().enumerate() {
+            dependency.serialize(&mut writer, dependencies_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(Dependency::ELEMENT_NAME.local_name),
+        )?;
+        let files_path = Path::new("files".into());
+        for (idx, file) in self.files.iter().enumerate() {
+            file.serialize(&mut writer, files_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(File::ELEMENT_NAME.local_name),
+        )?;
+        let clr_classes_path = Path::new("clr_classes".into());
+        for (idx, clr_class) in self.clr_classes.iter().enumerate() {
+            clr_class.serialize(&mut writer, clr_classes_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(ClrClass::ELEMENT_NAME.local_name),
+        )?;
+        let clr_surrogates_path = Path::new("clr_surrogates".into());
+        for (idx, clr_surrogate) in self.clr_surrogates.iter().enumerate() {
+            clr_surrogate.serialize(&mut writer, clr_surrogates_path.appended(idx.into()))?;
+        }
+        self.unknown.serialize_nodes(
+            &mut writer,
+            &unknown_path,
+            Some(ClrSurrogate::ELEMENT_NAME.local_name),
+        )?;
+        let extensions_path = Path::new("extensions".into());
+        for (idx, extension) in self.extensions.iter().enumerate() {
+            extension.serialize(&mut writer, extensions_path.appended(idx.into()))?;
+        }
 
         writer.write(XmlEvent::EndElement { name: None })?;
+        self.serialize_document_nodes(
+            &mut writer,
+            &document_nodes_path,
+            Some(AssemblyManifest::ELEMENT_NAME.local_name),
+        )?;
+
+        Ok(output)
+    }
+
+    fn serialize_document_nodes(
+        &self,
+        writer: &mut XmlWriter<'_>,
+        path: &Path<'_>,
+        after: Option<&str>,
+    ) -> SerializeResult<()> {
+        for (idx, node) in self.document_nodes.iter().enumerate() {
+            if node.after.as_deref() == after {
+                node.node.serialize(writer, path.appended(idx.into()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Implementation of common deserialization
+impl AssemblyManifest {
+    fn is_known_element(element: &Element) -> bool {
+        AssemblyManifest::is_known_name(&element.name)
+    }
+
+    /// Whether child of `assembly` named `name` is modeled by this crate
+    pub(crate) fn is_known_name(name: &OwnedName) -> bool {
+        let is = |known: &XmlName<'_>| {
+            name.local_name == known.local_name && name.namespace.as_deref() == known.namespace
+        };
+        is(&AssemblyIdentity::ELEMENT_NAME)
+            || is(&AssemblyManifest::ELEMENT_NO_INHERITABLE_NAME)
+            || is(&AssemblyManifest::ELEMENT_DESCRIPTION_NAME)
+            || is(&AssemblyManifest::ELEMENT_NO_INHERIT_NAME)
+            || TrustInfo::is_name(name, &TrustInfo::ELEMENT_NAME)
+            || is(&Compatibility::ELEMENT_NAME)
+            || is(&WindowsSettings::ELEMENT_APPLICATION_NAME)
+            || is(&ComInterfaceProxyStub::ELEMENT_EXTERNAL_NAME)
+            || is(&Dependency::ELEMENT_NAME)
+            || is(&File::ELEMENT_NAME)
+            || is(&ClrClass::ELEMENT_NAME)
+            || is(&ClrSurrogate::ELEMENT_NAME)
+    }
+
+    /// Parse manifest from reader
+    ///
+    /// Content which is not modeled by this crate is kept in `unknown` fields.
+    pub fn from_reader<R: Read>(reader: R) -> ParseResult<Self> {
+        let document = Element::read_document(reader)?;
+        let root = document.root;
+        if !root.is(&AssemblyManifest::ELEMENT_NAME) {
+            return Err(root.invalid(
+                &Path::new("assembly".into()),
+                format!("unexpected root element {}", root.name),
+            ));
+        }
+
+        let mut manifest = AssemblyManifest {
+            manifest_version: root
+                .required_value("manifestVersion", &Path::new("manifest_version".into()))?,
+            unknown: UnknownContent::capture(
+                &root,
+                AssemblyManifest::is_known_element,
+                &[AssemblyManifest::ATTRIBUTE_MANIFEST_VERSION_NAME.local_name],
+            ),
+            document_nodes: document
+                .comments
+                .into_iter()
+                .map(|(after_root, comment)| AnchoredNode {
+                    after: if after_root {
+                        Some(AssemblyManifest::ELEMENT_NAME.local_name.to_string())
+                    } else {
+                        None
+                    },
+                    node: UnknownNode::Comment(comment),
+                })
+                .collect(),
+            ..AssemblyManifest::default()
+        };
+
+        let proxy_stubs_path = Path::new("com_interface_external_proxy_stubs".into());
+        let dependencies_path = Path::new("dependencies".into());
+        let files_path = Path::new("files".into());
+        let clr_classes_path = Path::new("clr_classes".into());
+        let clr_surrogates_path = Path::new("clr_surrogates".into());
+        for child in root.elements() {
+            if child.is(&AssemblyIdentity::ELEMENT_NAME) {
+                let path = Path::new("assembly_identity".into());
+                if manifest.assembly_identity.is_some() {
+                    return Err(child.invalid(&path, "assemblyIdentity is duplicated"));
+                }
+                manifest.assembly_identity = Some(AssemblyIdentity::deserialize(child, path)?);
+            } else if child.is(&AssemblyManifest::ELEMENT_NO_INHERITABLE_NAME) {
+                manifest.no_inheritable = true;
+            } else if child.is(&AssemblyManifest::ELEMENT_DESCRIPTION_NAME) {
+                let path = Path::new("description".into());
+                if manifest.description.is_some() {
+                    return Err(child.invalid(&path, "description is duplicated"));
+                }
+                manifest.description = Some(child.text());
+            } else if child.is(&AssemblyManifest::ELEMENT_NO_INHERIT_NAME) {
+                manifest.no_inherit = true;
+            } else if TrustInfo::is_element(child, &TrustInfo::ELEMENT_NAME) {
+                manifest
+                    .trust_info
+                    .deserialize_merge(child, Path::new("trust_info".into()))?;
+            } else if child.is(&Compatibility::ELEMENT_NAME) {
+                manifest
+                    .compatibility
+                    .deserialize_merge(child, Path::new("compatibility".into()))?;
+            } else if child.is(&WindowsSettings::ELEMENT_APPLICATION_NAME) {
+                manifest
+                    .windows_settings
+                    .deserialize_merge(child, Path::new("windows_settings".into()))?;
+            } else if child.is(&ComInterfaceProxyStub::ELEMENT_EXTERNAL_NAME) {
+                let path = proxy_stubs_path
+                    .appended(manifest.com_interface_external_proxy_stubs.len().into());
+                manifest
+                    .com_interface_external_proxy_stubs
+                    .push(ComInterfaceProxyStub::deserialize(child, path)?);
+            } else if child.is(&Dependency::ELEMENT_NAME) {
+                let path = dependencies_path.appended(manifest.dependencies.len().into());
+                manifest
+                    .dependencies
+                    .push(Dependency::deserialize(child, path)?);
+            } else if child.is(&File::ELEMENT_NAME) {
+                let path = files_path.appended(manifest.files.len().into());
+                manifest.files.push(File::deserialize(child, path)?);
+            } else if child.is(&ClrClass::ELEMENT_NAME) {
+                let path = clr_classes_path.appended(manifest.clr_classes.len().into());
+                manifest
+                    .clr_classes
+                    .push(ClrClass::deserialize(child, path)?);
+            } else if child.is(&ClrSurrogate::ELEMENT_NAME) {
+                let path = clr_surrogates_path.appended(manifest.clr_surrogates.len().into());
+                manifest
+                    .clr_surrogates
+                    .push(ClrSurrogate::deserialize(child, path)?);
+            }
+        }
 
-        Ok(writer.into_inner())
+        Ok(manifest)
     }
 }
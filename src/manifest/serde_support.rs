@@ -0,0 +1,85 @@
+//! Serde representations of values.
+//!
+//! Values are written as they are in XML, such as `"1.0.0.0"` for [`AssemblyVersion`],
+//! `"6595B64144CCF1DF"` for [`PublicKeyToken`] and `"amd64"` for [`ProcessArchitecture`].
+//! [`SupportedOS`] is written by its name, such as `"Windows10"`, or GUID when custom.
+use super::{
+    ActiveCodePage, AssemblyType, AssemblyVersion, DpiAware, DpiAwareness, ExecutionLevel, Guid,
+    HashAlgorithm, HeapType, ManifestVersion, ProcessArchitecture, PublicKeyToken, SupportedOS,
+    SupportedOSSet, ThreadingModel, TypeLibFlag, TypeLibVersion, VersionRange,
+};
+use crate::{deserialize::DeserializableValue, serialize::SerializableValue};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! serde_as_xml_value {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&SerializableValue::serialize(self))
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <String as Deserialize>::deserialize(deserializer)?;
+                    <$ty as DeserializableValue>::deserialize(&value).ok_or_else(|| {
+                        de::Error::invalid_value(de::Unexpected::Str(&value), &stringify!($ty))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+serde_as_xml_value!(
+    ActiveCodePage,
+    AssemblyType,
+    AssemblyVersion,
+    DpiAware,
+    DpiAwareness,
+    ExecutionLevel,
+    Guid,
+    HashAlgorithm,
+    HeapType,
+    ManifestVersion,
+    ProcessArchitecture,
+    PublicKeyToken,
+    ThreadingModel,
+    TypeLibFlag,
+    TypeLibVersion,
+    VersionRange,
+);
+
+impl Serialize for SupportedOS {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.name() {
+            Some(name) => serializer.serialize_str(name),
+            None => serializer.serialize_str(&self.guid().to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SupportedOS {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as Deserialize>::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for SupportedOSSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SupportedOSSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            <Vec<SupportedOS> as Deserialize>::deserialize(deserializer)?
+                .into_iter()
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,333 @@
+use super::unknown::UnknownContent;
+use crate::xml_alias::{namespace, XmlAttribute, XmlName, XmlNamespace};
+use crate::{
+    consts::{NS_MS_ASM_V2, NS_MS_ASM_V3},
+    debug::Path,
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+};
+use std::borrow::Cow;
+use xml::{name::OwnedName, writer::XmlEvent};
+
+/// UAC execution level
+///
+/// reference [https://docs.microsoft.com/en-us/windows/security/identity-protection/user-account-control/how-user-account-control-works](https://docs.microsoft.com/en-us/windows/security/identity-protection/user-account-control/how-user-account-control-works)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ExecutionLevel {
+    /// Run with the same token as the parent process
+    AsInvoker,
+    /// Run with the highest privileges the current user can obtain
+    HighestAvailable,
+    /// Run only as an administrator
+    RequireAdministrator,
+}
+
+impl SerializableValue for ExecutionLevel {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            ExecutionLevel::AsInvoker => "asInvoker",
+            ExecutionLevel::HighestAvailable => "highestAvailable",
+            ExecutionLevel::RequireAdministrator => "requireAdministrator",
+        })
+    }
+}
+
+impl DeserializableValue for ExecutionLevel {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "asInvoker" => Some(ExecutionLevel::AsInvoker),
+            "highestAvailable" => Some(ExecutionLevel::HighestAvailable),
+            "requireAdministrator" => Some(ExecutionLevel::RequireAdministrator),
+            _ => None,
+        }
+    }
+}
+
+/// Requested UAC privileges
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RequestedExecutionLevel {
+    #[allow(missing_docs)]
+    pub level: ExecutionLevel,
+    /// Whether application bypasses UI protection levels to drive input to higher privilege windows.
+    ///
+    /// Only accessibility applications signed and installed in a secure location should set this.
+    pub ui_access: Option<bool>,
+}
+
+impl RequestedExecutionLevel {
+    #[allow(missing_docs)]
+    pub const fn new(level: ExecutionLevel) -> Self {
+        RequestedExecutionLevel {
+            level,
+            ui_access: Some(false),
+        }
+    }
+}
+
+/// Security requirements of application
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TrustInfo {
+    /// `None` means UAC virtualization applies to the application
+    pub requested_execution_level: Option<RequestedExecutionLevel>,
+    /// Content of `trustInfo` element which is not modeled, such as `applicationRequestMinimum` of ClickOnce.
+    ///
+    /// Content of inner `security` and `requestedPrivileges` elements is kept in [`UnknownContent::nested`](struct.UnknownContent.html#structfield.nested).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl TrustInfo {
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "trustInfo",
+        namespace: Some(NS_MS_ASM_V2),
+        prefix: None,
+    };
+    const ELEMENT_SECURITY_NAME: XmlName<'static> = XmlName {
+        local_name: "security",
+        namespace: Some(NS_MS_ASM_V2),
+        prefix: None,
+    };
+    const ELEMENT_REQUESTED_PRIVILEGES_NAME: XmlName<'static> = XmlName {
+        local_name: "requestedPrivileges",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+    const ELEMENT_REQUESTED_EXECUTION_LEVEL_NAME: XmlName<'static> = XmlName {
+        local_name: "requestedExecutionLevel",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+    const ATTRIBUTE_LEVEL_NAME: XmlName<'static> = XmlName {
+        local_name: "level",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+    const ATTRIBUTE_UI_ACCESS_NAME: XmlName<'static> = XmlName {
+        local_name: "uiAccess",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+
+    /// `trustInfo` and its descendants are written with either asm.v2 or asm.v3 namespace
+    pub(crate) fn is_element(element: &Element, name: &XmlName<'_>) -> bool {
+        TrustInfo::is_name(&element.name, name)
+    }
+
+    pub(crate) fn is_name(name: &OwnedName, expected: &XmlName<'_>) -> bool {
+        name.local_name == expected.local_name
+            && matches!(
+                name.namespace.as_deref(),
+                Some(NS_MS_ASM_V2) | Some(NS_MS_ASM_V3)
+            )
+    }
+}
+
+impl Validatable for TrustInfo {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if let Some(RequestedExecutionLevel {
+            ui_access: Some(true),
+            ..
+        }) = &self.requested_execution_level
+        {
+            let requested_execution_level_path = path.appended("requested_execution_level".into());
+            problems.warning(
+                &requested_execution_level_path.appended("ui_access".into()),
+                "ui-access",
+                "application with ui_access must be signed and installed in a secure location",
+                "set ui_access to false unless the application is an accessibility tool",
+            );
+        }
+    }
+}
+
+impl SerializableElement for TrustInfo {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        if self.requested_execution_level.is_none() && self.unknown.is_empty() {
+            return Ok(());
+        }
+
+        let unknown_path = path.appended("unknown".into());
+        let mut attributes = Vec::new();
+        self.unknown.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: TrustInfo::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace({
+                let mut ns = XmlNamespace::empty();
+                ns.put(namespace::NS_NO_PREFIX.to_string(), NS_MS_ASM_V2);
+                ns
+            })),
+        })?;
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        let security = self
+            .unknown
+            .nested(TrustInfo::ELEMENT_SECURITY_NAME.local_name);
+        let requested_privileges = self
+            .unknown
+            .nested(TrustInfo::ELEMENT_REQUESTED_PRIVILEGES_NAME.local_name);
+        if self.requested_execution_level.is_some()
+            || !security.is_empty()
+            || !requested_privileges.is_empty()
+        {
+            let security_path = unknown_path.appended("security".into());
+            let mut attributes = Vec::new();
+            security.extend_attributes(&mut attributes);
+            writer.write(XmlEvent::StartElement {
+                name: TrustInfo::ELEMENT_SECURITY_NAME,
+                attributes: Cow::Borrowed(&attributes),
+                namespace: Cow::Owned(security.namespace(XmlNamespace::empty())),
+            })?;
+            security.serialize_nodes(writer, &security_path, None)?;
+
+            if self.requested_execution_level.is_some() || !requested_privileges.is_empty() {
+                self.serialize_requested_privileges(
+                    writer,
+                    requested_privileges,
+                    unknown_path.appended("requested_privileges".into()),
+                )?;
+            }
+            security.serialize_nodes(
+                writer,
+                &security_path,
+                Some(TrustInfo::ELEMENT_REQUESTED_PRIVILEGES_NAME.local_name),
+            )?;
+
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(TrustInfo::ELEMENT_SECURITY_NAME.local_name),
+        )?;
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl TrustInfo {
+    fn serialize_requested_privileges(
+        &self,
+        writer: &mut XmlWriter<'_>,
+        unknown: &UnknownContent,
+        unknown_path: Path<'_>,
+    ) -> SerializeResult<()> {
+        let mut attributes = Vec::new();
+        unknown.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: TrustInfo::ELEMENT_REQUESTED_PRIVILEGES_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(unknown.namespace({
+                let mut ns = XmlNamespace::empty();
+                ns.put(namespace::NS_NO_PREFIX.to_string(), NS_MS_ASM_V3);
+                ns
+            })),
+        })?;
+        unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        if let Some(requested_execution_level) = &self.requested_execution_level {
+            let mut attributes = Vec::<XmlAttribute>::new();
+            let level = requested_execution_level.level.serialize();
+            attributes.push(XmlAttribute {
+                name: TrustInfo::ATTRIBUTE_LEVEL_NAME,
+                value: &level,
+            });
+            let ui_access = requested_execution_level
+                .ui_access
+                .as_ref()
+                .map(|ui_access| ui_access.serialize());
+            if let Some(ui_access) = &ui_access {
+                attributes.push(XmlAttribute {
+                    name: TrustInfo::ATTRIBUTE_UI_ACCESS_NAME,
+                    value: ui_access,
+                });
+            }
+            writer.write(XmlEvent::StartElement {
+                name: TrustInfo::ELEMENT_REQUESTED_EXECUTION_LEVEL_NAME,
+                attributes: Cow::Borrowed(&attributes),
+                namespace: Cow::Owned(XmlNamespace::empty()),
+            })?;
+            writer.write(XmlEvent::EndElement { name: None })?;
+        }
+        unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(TrustInfo::ELEMENT_REQUESTED_EXECUTION_LEVEL_NAME.local_name),
+        )?;
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for TrustInfo {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let mut trust_info = TrustInfo::default();
+        trust_info.deserialize_merge(element, path)?;
+        Ok(trust_info)
+    }
+}
+
+impl TrustInfo {
+    /// Merge `trustInfo` element, which can be repeated in manifest
+    pub(crate) fn deserialize_merge(
+        &mut self,
+        element: &Element,
+        path: Path<'_>,
+    ) -> ParseResult<()> {
+        let trust_info = self;
+        trust_info.unknown.merge(UnknownContent::capture(
+            element,
+            |child| TrustInfo::is_element(child, &TrustInfo::ELEMENT_SECURITY_NAME),
+            &[],
+        ));
+
+        let path = path.appended("requested_execution_level".into());
+        let mut levels = Vec::new();
+        for security in element
+            .elements()
+            .filter(|child| TrustInfo::is_element(child, &TrustInfo::ELEMENT_SECURITY_NAME))
+        {
+            trust_info.unknown.capture_nested(
+                security,
+                |child| TrustInfo::is_element(child, &TrustInfo::ELEMENT_REQUESTED_PRIVILEGES_NAME),
+                &[],
+            );
+            for privileges in security.elements().filter(|child| {
+                TrustInfo::is_element(child, &TrustInfo::ELEMENT_REQUESTED_PRIVILEGES_NAME)
+            }) {
+                trust_info.unknown.capture_nested(
+                    privileges,
+                    |child| {
+                        TrustInfo::is_element(
+                            child,
+                            &TrustInfo::ELEMENT_REQUESTED_EXECUTION_LEVEL_NAME,
+                        )
+                    },
+                    &[],
+                );
+                levels.extend(privileges.elements().filter(|child| {
+                    TrustInfo::is_element(child, &TrustInfo::ELEMENT_REQUESTED_EXECUTION_LEVEL_NAME)
+                }));
+            }
+        }
+        for level in levels {
+            if trust_info.requested_execution_level.is_some() {
+                return Err(level.invalid(&path, "requestedExecutionLevel is duplicated"));
+            }
+            trust_info.requested_execution_level = Some(RequestedExecutionLevel {
+                level: level.required_value("level", &path.appended("level".into()))?,
+                ui_access: level.value("uiAccess", &path.appended("ui_access".into()))?,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::xml_alias::{namespace, XmlAttribute, XmlNamespace};
+use crate::{
+    debug::Path,
+    deserialize::{Element, Node},
+    serialize::{SerializableElement, SerializeResult, XmlWriter},
+};
+use std::{borrow::Cow, collections::BTreeMap};
+use xml::{attribute::OwnedAttribute, name::OwnedName, writer::XmlEvent};
+
+/// XML node which is not modeled by this crate
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UnknownNode {
+    #[allow(missing_docs)]
+    Element(UnknownElement),
+    #[allow(missing_docs)]
+    Comment(String),
+    #[allow(missing_docs)]
+    Characters(String),
+}
+
+/// XML element which is not modeled by this crate
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnknownElement {
+    #[allow(missing_docs)]
+    pub name: OwnedName,
+    /// Namespaces declared on this element, pairs of prefix and URI
+    pub namespaces: Vec<(String, String)>,
+    #[allow(missing_docs)]
+    pub attributes: Vec<OwnedAttribute>,
+    #[allow(missing_docs)]
+    pub children: Vec<UnknownNode>,
+}
+
+/// Unknown node with its position among modeled elements
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AnchoredNode {
+    /// Local name of the nearest modeled element preceding this node.
+    ///
+    /// `None` when no modeled element precedes this node.
+    pub after: Option<String>,
+    #[allow(missing_docs)]
+    pub node: UnknownNode,
+}
+
+/// Content of a modeled element which is not modeled by this crate
+///
+/// It is captured while parsing and re-emitted in place while serializing,
+/// so editing a parsed manifest doesn't drop foreign elements, attributes, namespaces and comments.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct UnknownContent {
+    /// Prefixed namespaces declared on the element, pairs of prefix and URI
+    pub namespaces: Vec<(String, String)>,
+    /// Unknown attributes of the element
+    pub attributes: Vec<OwnedAttribute>,
+    /// Unknown child nodes of the element
+    pub nodes: Vec<AnchoredNode>,
+    /// Unknown content of modeled descendants which have no type of their own(e.g. `dependentAssembly`), by local name
+    pub nested: BTreeMap<String, UnknownContent>,
+}
+
+static EMPTY: UnknownContent = UnknownContent {
+    namespaces: Vec::new(),
+    attributes: Vec::new(),
+    nodes: Vec::new(),
+    nested: BTreeMap::new(),
+};
+
+impl UnknownContent {
+    /// Whether nothing is captured
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+            && self.attributes.is_empty()
+            && self.nodes.is_empty()
+            && self.nested.values().all(UnknownContent::is_empty)
+    }
+
+    /// Unknown content of modeled descendant
+    pub fn nested(&self, local_name: &str) -> &UnknownContent {
+        self.nested.get(local_name).unwrap_or(&EMPTY)
+    }
+
+    /// Capture unknown content of modeled element
+    pub(crate) fn capture<F: Fn(&Element) -> bool>(
+        element: &Element,
+        is_known: F,
+        known_attributes: &[&str],
+    ) -> UnknownContent {
+        let mut content = UnknownContent {
+            namespaces: element
+                .namespaces
+                .iter()
+                .filter(|(prefix, _)| prefix != namespace::NS_NO_PREFIX)
+                .cloned()
+                .collect(),
+            attributes: element
+                .attributes
+                .iter()
+                .filter(|attribute| {
+                    attribute.name.namespace.is_some()
+                        || !known_attributes.contains(&attribute.name.local_name.as_str())
+                })
+                .cloned()
+                .collect(),
+            ..UnknownContent::default()
+        };
+
+        let mut after = None;
+        for child in &element.children {
+            match child {
+                Node::Element(child) if is_known(child) => {
+                    after = Some(child.name.local_name.clone());
+                }
+                Node::Characters(_) => {}
+                child => content.nodes.push(AnchoredNode {
+                    after: after.clone(),
+                    node: UnknownNode::capture(child),
+                }),
+            }
+        }
+
+        content
+    }
+
+    /// Capture unknown content of modeled descendant, which has no type of its own
+    pub(crate) fn capture_nested<F: Fn(&Element) -> bool>(
+        &mut self,
+        element: &Element,
+        is_known: F,
+        known_attributes: &[&str],
+    ) {
+        let content = UnknownContent::capture(element, is_known, known_attributes);
+        if !content.is_empty() {
+            self.nested
+                .entry(element.name.local_name.clone())
+                .or_default()
+                .merge(content);
+        }
+    }
+
+    /// Merge content captured from repeated element, such as second `application`
+    pub(crate) fn merge(&mut self, other: UnknownContent) {
+        for declaration in other.namespaces {
+            if !self.namespaces.contains(&declaration) {
+                self.namespaces.push(declaration);
+            }
+        }
+        for attribute in other.attributes {
+            if !self
+                .attributes
+                .iter()
+                .any(|existing| existing.name == attribute.name)
+            {
+                self.attributes.push(attribute);
+            }
+        }
+        self.nodes.extend(other.nodes);
+        for (name, nested) in other.nested {
+            self.nested.entry(name).or_default().merge(nested);
+        }
+    }
+
+    /// Add namespace declarations into namespace of modeled element
+    pub(crate) fn namespace(&self, mut namespace: XmlNamespace) -> XmlNamespace {
+        for (prefix, uri) in &self.namespaces {
+            namespace.put(prefix.as_str(), uri.as_str());
+        }
+        namespace
+    }
+
+    /// Add unknown attributes into attributes of modeled element
+    pub(crate) fn extend_attributes<'a>(&'a self, attributes: &mut Vec<XmlAttribute<'a>>) {
+        attributes.extend(self.attributes.iter().map(OwnedAttribute::borrow));
+    }
+
+    /// Write unknown nodes which follow given modeled element
+    pub(crate) fn serialize_nodes(
+        &self,
+        writer: &mut XmlWriter<'_>,
+        path: &Path<'_>,
+        after: Option<&str>,
+    ) -> SerializeResult<()> {
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if node.after.as_deref() == after {
+                node.node.serialize(writer, path.appended(idx.into()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl UnknownNode {
+    pub(crate) fn capture(node: &Node) -> UnknownNode {
+        match node {
+            Node::Element(element) => UnknownNode::Element(UnknownElement {
+                name: element.name.clone(),
+                namespaces: element.namespaces.clone(),
+                attributes: element.attributes.clone(),
+                children: element.children.iter().map(UnknownNode::capture).collect(),
+            }),
+            Node::Characters(characters) => UnknownNode::Characters(characters.clone()),
+            Node::Comment(comment) => UnknownNode::Comment(comment.clone()),
+        }
+    }
+}
+
+impl SerializableElement for UnknownNode {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        match self {
+            UnknownNode::Element(element) => {
+                let mut ns = XmlNamespace::empty();
+                for (prefix, uri) in &element.namespaces {
+                    ns.put(prefix.as_str(), uri.as_str());
+                }
+                // Declare namespace of element itself, in case it is not declared in the new context
+                ns.put(
+                    element
+                        .name
+                        .prefix
+                        .as_deref()
+                        .unwrap_or(namespace::NS_NO_PREFIX),
+                    element
+                        .name
+                        .namespace
+                        .as_deref()
+                        .unwrap_or(namespace::NS_EMPTY_URI),
+                );
+
+                writer.write(XmlEvent::StartElement {
+                    name: element.name.borrow(),
+                    attributes: Cow::Owned(
+                        element
+                            .attributes
+                            .iter()
+                            .map(OwnedAttribute::borrow)
+                            .collect(),
+                    ),
+                    namespace: Cow::Owned(ns),
+                })?;
+                for (idx, child) in element.children.iter().enumerate() {
+                    child.serialize(writer, path.appended(idx.into()))?;
+                }
+                writer.write(XmlEvent::EndElement { name: None })?;
+            }
+            UnknownNode::Comment(comment) => writer.write(XmlEvent::Comment(comment))?,
+            UnknownNode::Characters(characters) => {
+                writer.write(XmlEvent::Characters(characters))?
+            }
+        }
+
+        Ok(())
+    }
+}
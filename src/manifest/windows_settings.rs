@@ -0,0 +1,549 @@
+use super::unknown::UnknownContent;
+use crate::xml_alias::{namespace, XmlName, XmlNamespace};
+use crate::{
+    consts::{
+        NS_MS_ASM_V3, NS_SMI_2005, NS_SMI_2011, NS_SMI_2016, NS_SMI_2017, NS_SMI_2019, NS_SMI_2020,
+    },
+    debug::Path,
+    deserialize::{DeserializableElement, DeserializableValue, Element, ParseResult},
+    serialize::{SerializableElement, SerializableValue, SerializeResult, XmlWriter},
+    validate::{Problems, Validatable},
+};
+use std::borrow::Cow;
+use xml::writer::XmlEvent;
+
+/// Legacy DPI awareness setting (`dpiAware`)
+///
+/// Ignored by Windows 10 1607 and later when [`WindowsSettings::dpi_awareness`](struct.WindowsSettings.html#structfield.dpi_awareness) is set.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DpiAware {
+    /// `false`
+    False,
+    /// `true`
+    True,
+    /// `true/pm`
+    TruePerMonitor,
+    /// `per monitor`
+    PerMonitor,
+}
+
+impl SerializableValue for DpiAware {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            DpiAware::False => "false",
+            DpiAware::True => "true",
+            DpiAware::TruePerMonitor => "true/pm",
+            DpiAware::PerMonitor => "per monitor",
+        })
+    }
+}
+
+impl DeserializableValue for DpiAware {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "false" => Some(DpiAware::False),
+            "true" => Some(DpiAware::True),
+            "true/pm" => Some(DpiAware::TruePerMonitor),
+            "per monitor" => Some(DpiAware::PerMonitor),
+            _ => None,
+        }
+    }
+}
+
+/// DPI awareness mode (`dpiAwareness`)
+///
+/// reference [https://docs.microsoft.com/en-us/windows/win32/sbscs/application-manifests#dpiawareness](https://docs.microsoft.com/en-us/windows/win32/sbscs/application-manifests#dpiawareness)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DpiAwareness {
+    /// `unaware`
+    Unaware,
+    /// `system`
+    System,
+    /// `permonitor`
+    PerMonitor,
+    /// `permonitorv2`
+    PerMonitorV2,
+}
+
+impl SerializableValue for DpiAwareness {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            DpiAwareness::Unaware => "unaware",
+            DpiAwareness::System => "system",
+            DpiAwareness::PerMonitor => "permonitor",
+            DpiAwareness::PerMonitorV2 => "permonitorv2",
+        })
+    }
+}
+
+impl DeserializableValue for DpiAwareness {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "unaware" => Some(DpiAwareness::Unaware),
+            "system" => Some(DpiAwareness::System),
+            "permonitor" => Some(DpiAwareness::PerMonitor),
+            "permonitorv2" => Some(DpiAwareness::PerMonitorV2),
+            _ => None,
+        }
+    }
+}
+
+/// Process code page (`activeCodePage`)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ActiveCodePage {
+    /// `UTF-8`
+    Utf8,
+    /// `Legacy`, the system code page
+    Legacy,
+    /// Code page of given locale name. e.g. `en-US`
+    Locale(String),
+}
+
+impl SerializableValue for ActiveCodePage {
+    fn serialize(&self) -> Cow<'_, str> {
+        match self {
+            ActiveCodePage::Utf8 => Cow::Borrowed("UTF-8"),
+            ActiveCodePage::Legacy => Cow::Borrowed("Legacy"),
+            ActiveCodePage::Locale(locale) => Cow::Borrowed(locale),
+        }
+    }
+}
+
+impl DeserializableValue for ActiveCodePage {
+    fn deserialize(value: &str) -> Option<Self> {
+        Some(match value {
+            "UTF-8" => ActiveCodePage::Utf8,
+            "Legacy" => ActiveCodePage::Legacy,
+            locale => ActiveCodePage::Locale(locale.to_string()),
+        })
+    }
+}
+
+/// Heap implementation (`heapType`)
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum HeapType {
+    /// `SegmentHeap`
+    SegmentHeap,
+}
+
+impl SerializableValue for HeapType {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
+            HeapType::SegmentHeap => "SegmentHeap",
+        })
+    }
+}
+
+impl DeserializableValue for HeapType {
+    fn deserialize(value: &str) -> Option<Self> {
+        match value {
+            "SegmentHeap" => Some(HeapType::SegmentHeap),
+            _ => None,
+        }
+    }
+}
+
+/// Windows settings of application
+///
+/// Each setting is emitted under the namespace of the Windows release that introduced it.
+/// `None`(or empty) settings are not emitted.
+///
+/// reference [https://docs.microsoft.com/en-us/windows/win32/sbscs/application-manifests](https://docs.microsoft.com/en-us/windows/win32/sbscs/application-manifests)
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WindowsSettings {
+    /// Process code page. Windows 10 1903 or later
+    pub active_code_page: Option<ActiveCodePage>,
+    #[allow(missing_docs)]
+    pub auto_elevate: Option<bool>,
+    /// Disable visual styles
+    pub disable_theming: Option<bool>,
+    /// Enumerate immersive windows from `EnumWindows`
+    pub disable_window_filtering: Option<bool>,
+    #[allow(missing_docs)]
+    pub dpi_aware: Option<DpiAware>,
+    /// DPI awareness modes, in order of preference. Windows 10 1607 or later
+    pub dpi_awareness: Vec<DpiAwareness>,
+    /// GDI scaling for DPI unaware application. Windows 10 1703 or later
+    pub gdi_scaling: Option<bool>,
+    /// Windows 10 2004 or later
+    pub heap_type: Option<HeapType>,
+    #[allow(missing_docs)]
+    pub high_resolution_scrolling_aware: Option<bool>,
+    /// Allow paths longer than `MAX_PATH`. Windows 10 1607 or later
+    pub long_path_aware: Option<bool>,
+    /// Improve printer driver compatibility. Windows 7 or later
+    pub printer_driver_isolation: Option<bool>,
+    #[allow(missing_docs)]
+    pub ultra_high_resolution_scrolling_aware: Option<bool>,
+    /// Content of `application` element which is not modeled.
+    ///
+    /// Content of inner `windowsSettings` element, including settings unknown to this crate,
+    /// is kept in [`UnknownContent::nested`](struct.UnknownContent.html#structfield.nested).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub unknown: UnknownContent,
+}
+
+impl WindowsSettings {
+    pub(crate) const ELEMENT_APPLICATION_NAME: XmlName<'static> = XmlName {
+        local_name: "application",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+    pub(crate) const ELEMENT_NAME: XmlName<'static> = XmlName {
+        local_name: "windowsSettings",
+        namespace: Some(NS_MS_ASM_V3),
+        prefix: None,
+    };
+    const ELEMENT_ACTIVE_CODE_PAGE_NAME: XmlName<'static> = XmlName {
+        local_name: "activeCodePage",
+        namespace: Some(NS_SMI_2019),
+        prefix: None,
+    };
+    const ELEMENT_AUTO_ELEVATE_NAME: XmlName<'static> = XmlName {
+        local_name: "autoElevate",
+        namespace: Some(NS_SMI_2005),
+        prefix: None,
+    };
+    const ELEMENT_DISABLE_THEMING_NAME: XmlName<'static> = XmlName {
+        local_name: "disableTheming",
+        namespace: Some(NS_SMI_2005),
+        prefix: None,
+    };
+    const ELEMENT_DISABLE_WINDOW_FILTERING_NAME: XmlName<'static> = XmlName {
+        local_name: "disableWindowFiltering",
+        namespace: Some(NS_SMI_2011),
+        prefix: None,
+    };
+    const ELEMENT_DPI_AWARE_NAME: XmlName<'static> = XmlName {
+        local_name: "dpiAware",
+        namespace: Some(NS_SMI_2005),
+        prefix: None,
+    };
+    const ELEMENT_DPI_AWARENESS_NAME: XmlName<'static> = XmlName {
+        local_name: "dpiAwareness",
+        namespace: Some(NS_SMI_2016),
+        prefix: None,
+    };
+    const ELEMENT_GDI_SCALING_NAME: XmlName<'static> = XmlName {
+        local_name: "gdiScaling",
+        namespace: Some(NS_SMI_2017),
+        prefix: None,
+    };
+    const ELEMENT_HEAP_TYPE_NAME: XmlName<'static> = XmlName {
+        local_name: "heapType",
+        namespace: Some(NS_SMI_2020),
+        prefix: None,
+    };
+    const ELEMENT_HIGH_RESOLUTION_SCROLLING_AWARE_NAME: XmlName<'static> = XmlName {
+        local_name: "highResolutionScrollingAware",
+        namespace: Some(NS_SMI_2005),
+        prefix: None,
+    };
+    const ELEMENT_LONG_PATH_AWARE_NAME: XmlName<'static> = XmlName {
+        local_name: "longPathAware",
+        namespace: Some(NS_SMI_2016),
+        prefix: None,
+    };
+    const ELEMENT_PRINTER_DRIVER_ISOLATION_NAME: XmlName<'static> = XmlName {
+        local_name: "printerDriverIsolation",
+        namespace: Some(NS_SMI_2011),
+        prefix: None,
+    };
+    const ELEMENT_ULTRA_HIGH_RESOLUTION_SCROLLING_AWARE_NAME: XmlName<'static> = XmlName {
+        local_name: "ultraHighResolutionScrollingAware",
+        namespace: Some(NS_SMI_2005),
+        prefix: None,
+    };
+
+    /// Whether any setting is specified
+    pub fn is_empty(&self) -> bool {
+        self == &WindowsSettings::default()
+    }
+
+    fn write_setting(
+        writer: &mut XmlWriter<'_>,
+        name: XmlName<'static>,
+        value: &str,
+    ) -> SerializeResult<()> {
+        writer.write(XmlEvent::StartElement {
+            name,
+            attributes: Cow::Borrowed(&[]),
+            namespace: Cow::Owned({
+                let mut ns = XmlNamespace::empty();
+                ns.put(namespace::NS_NO_PREFIX.to_string(), name.namespace.unwrap());
+                ns
+            }),
+        })?;
+        writer.write(XmlEvent::Characters(value))?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl Validatable for WindowsSettings {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        if let Some(ActiveCodePage::Locale(locale)) = &self.active_code_page {
+            if locale.is_empty() {
+                problems.error(
+                    &path.appended("active_code_page".into()),
+                    "empty-locale",
+                    "locale name of active_code_page is empty",
+                    "use ActiveCodePage::Utf8, ActiveCodePage::Legacy or locale name such as `en-US`",
+                );
+            }
+        }
+        let dpi_awareness_path = path.appended("dpi_awareness".into());
+        for (idx, awareness) in self.dpi_awareness.iter().enumerate() {
+            if self.dpi_awareness[..idx].contains(awareness) {
+                problems.error(
+                    &dpi_awareness_path.appended(idx.into()),
+                    "duplicate-dpi-awareness",
+                    format!("{} is duplicated", awareness.serialize()),
+                    "remove duplicated value",
+                );
+            }
+        }
+    }
+}
+
+impl SerializableElement for WindowsSettings {
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let dpi_awareness = if self.dpi_awareness.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(
+                self.dpi_awareness
+                    .iter()
+                    .map(|awareness| awareness.serialize())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ))
+        };
+        let settings = [
+            (
+                WindowsSettings::ELEMENT_ACTIVE_CODE_PAGE_NAME,
+                self.active_code_page
+                    .as_ref()
+                    .map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_AUTO_ELEVATE_NAME,
+                self.auto_elevate.as_ref().map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_DISABLE_THEMING_NAME,
+                self.disable_theming.as_ref().map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_DISABLE_WINDOW_FILTERING_NAME,
+                self.disable_window_filtering
+                    .as_ref()
+                    .map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_DPI_AWARE_NAME,
+                self.dpi_aware.as_ref().map(|value| value.serialize()),
+            ),
+            (WindowsSettings::ELEMENT_DPI_AWARENESS_NAME, dpi_awareness),
+            (
+                WindowsSettings::ELEMENT_GDI_SCALING_NAME,
+                self.gdi_scaling.as_ref().map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_HEAP_TYPE_NAME,
+                self.heap_type.as_ref().map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_HIGH_RESOLUTION_SCROLLING_AWARE_NAME,
+                self.high_resolution_scrolling_aware
+                    .as_ref()
+                    .map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_LONG_PATH_AWARE_NAME,
+                self.long_path_aware.as_ref().map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_PRINTER_DRIVER_ISOLATION_NAME,
+                self.printer_driver_isolation
+                    .as_ref()
+                    .map(|value| value.serialize()),
+            ),
+            (
+                WindowsSettings::ELEMENT_ULTRA_HIGH_RESOLUTION_SCROLLING_AWARE_NAME,
+                self.ultra_high_resolution_scrolling_aware
+                    .as_ref()
+                    .map(|value| value.serialize()),
+            ),
+        ];
+
+        let unknown_path = path.appended("unknown".into());
+        let mut attributes = Vec::new();
+        self.unknown.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: WindowsSettings::ELEMENT_APPLICATION_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(self.unknown.namespace({
+                let mut ns = XmlNamespace::empty();
+                ns.put(namespace::NS_NO_PREFIX.to_string(), NS_MS_ASM_V3);
+                ns
+            })),
+        })?;
+        self.unknown.serialize_nodes(writer, &unknown_path, None)?;
+
+        let windows_settings = self
+            .unknown
+            .nested(WindowsSettings::ELEMENT_NAME.local_name);
+        let windows_settings_path = unknown_path.appended("windowsSettings".into());
+        let mut attributes = Vec::new();
+        windows_settings.extend_attributes(&mut attributes);
+        writer.write(XmlEvent::StartElement {
+            name: WindowsSettings::ELEMENT_NAME,
+            attributes: Cow::Borrowed(&attributes),
+            namespace: Cow::Owned(windows_settings.namespace(XmlNamespace::empty())),
+        })?;
+        windows_settings.serialize_nodes(writer, &windows_settings_path, None)?;
+
+        for (name, value) in &settings {
+            if let Some(value) = value {
+                WindowsSettings::write_setting(writer, *name, value)?;
+            }
+            windows_settings.serialize_nodes(
+                writer,
+                &windows_settings_path,
+                Some(name.local_name),
+            )?;
+        }
+
+        writer.write(XmlEvent::EndElement { name: None })?;
+        self.unknown.serialize_nodes(
+            writer,
+            &unknown_path,
+            Some(WindowsSettings::ELEMENT_NAME.local_name),
+        )?;
+        writer.write(XmlEvent::EndElement { name: None })?;
+
+        Ok(())
+    }
+}
+
+impl DeserializableElement for WindowsSettings {
+    fn deserialize(element: &Element, path: Path<'_>) -> ParseResult<Self> {
+        let mut settings = WindowsSettings::default();
+        settings.deserialize_merge(element, path)?;
+        Ok(settings)
+    }
+}
+
+impl WindowsSettings {
+    /// Merge settings of `application` element, which can be repeated in manifest
+    pub(crate) fn deserialize_merge(
+        &mut self,
+        element: &Element,
+        path: Path<'_>,
+    ) -> ParseResult<()> {
+        self.unknown.merge(UnknownContent::capture(
+            element,
+            |child| child.is(&WindowsSettings::ELEMENT_NAME),
+            &[],
+        ));
+
+        for windows_settings in element
+            .elements()
+            .filter(|child| child.is(&WindowsSettings::ELEMENT_NAME))
+        {
+            self.deserialize_settings(windows_settings, &path)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_known_setting(element: &Element) -> bool {
+        [
+            WindowsSettings::ELEMENT_ACTIVE_CODE_PAGE_NAME,
+            WindowsSettings::ELEMENT_AUTO_ELEVATE_NAME,
+            WindowsSettings::ELEMENT_DISABLE_THEMING_NAME,
+            WindowsSettings::ELEMENT_DISABLE_WINDOW_FILTERING_NAME,
+            WindowsSettings::ELEMENT_DPI_AWARE_NAME,
+            WindowsSettings::ELEMENT_DPI_AWARENESS_NAME,
+            WindowsSettings::ELEMENT_GDI_SCALING_NAME,
+            WindowsSettings::ELEMENT_HEAP_TYPE_NAME,
+            WindowsSettings::ELEMENT_HIGH_RESOLUTION_SCROLLING_AWARE_NAME,
+            WindowsSettings::ELEMENT_LONG_PATH_AWARE_NAME,
+            WindowsSettings::ELEMENT_PRINTER_DRIVER_ISOLATION_NAME,
+            WindowsSettings::ELEMENT_ULTRA_HIGH_RESOLUTION_SCROLLING_AWARE_NAME,
+        ]
+        .iter()
+        .any(|name| element.is(name))
+    }
+
+    fn deserialize_settings(&mut self, element: &Element, path: &Path<'_>) -> ParseResult<()> {
+        let settings = self;
+        settings
+            .unknown
+            .capture_nested(element, WindowsSettings::is_known_setting, &[]);
+
+        for child in element.elements() {
+            if child.is(&WindowsSettings::ELEMENT_ACTIVE_CODE_PAGE_NAME) {
+                settings.active_code_page =
+                    Some(child.text_value(&path.appended("active_code_page".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_AUTO_ELEVATE_NAME) {
+                settings.auto_elevate =
+                    Some(child.text_value(&path.appended("auto_elevate".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_DISABLE_THEMING_NAME) {
+                settings.disable_theming =
+                    Some(child.text_value(&path.appended("disable_theming".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_DISABLE_WINDOW_FILTERING_NAME) {
+                settings.disable_window_filtering =
+                    Some(child.text_value(&path.appended("disable_window_filtering".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_DPI_AWARE_NAME) {
+                settings.dpi_aware = Some(child.text_value(&path.appended("dpi_aware".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_DPI_AWARENESS_NAME) {
+                let path = path.appended("dpi_awareness".into());
+                settings.dpi_awareness = child
+                    .text()
+                    .split(',')
+                    .enumerate()
+                    .map(|(idx, awareness)| {
+                        DpiAwareness::deserialize(awareness.trim()).ok_or_else(|| {
+                            child.invalid(
+                                &path.appended(idx.into()),
+                                format!("unexpected value of dpiAwareness - {}", awareness),
+                            )
+                        })
+                    })
+                    .collect::<ParseResult<_>>()?;
+            } else if child.is(&WindowsSettings::ELEMENT_GDI_SCALING_NAME) {
+                settings.gdi_scaling =
+                    Some(child.text_value(&path.appended("gdi_scaling".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_HEAP_TYPE_NAME) {
+                settings.heap_type = Some(child.text_value(&path.appended("heap_type".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_HIGH_RESOLUTION_SCROLLING_AWARE_NAME) {
+                settings.high_resolution_scrolling_aware = Some(
+                    child.text_value(&path.appended("high_resolution_scrolling_aware".into()))?,
+                );
+            } else if child.is(&WindowsSettings::ELEMENT_LONG_PATH_AWARE_NAME) {
+                settings.long_path_aware =
+                    Some(child.text_value(&path.appended("long_path_aware".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_PRINTER_DRIVER_ISOLATION_NAME) {
+                settings.printer_driver_isolation =
+                    Some(child.text_value(&path.appended("printer_driver_isolation".into()))?);
+            } else if child.is(&WindowsSettings::ELEMENT_ULTRA_HIGH_RESOLUTION_SCROLLING_AWARE_NAME)
+            {
+                settings.ultra_high_resolution_scrolling_aware =
+                    Some(child.text_value(
+                        &path.appended("ultra_high_resolution_scrolling_aware".into()),
+                    )?);
+            }
+        }
+
+        Ok(())
+    }
+}
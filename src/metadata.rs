@@ -0,0 +1,270 @@
+//! Manifest settings in `Cargo.toml`
+//!
+//! Settings are read from `[package.metadata.sxs-manifest]`, in the form of
+//! [serde representation](../index.html#serde) of [`AssemblyManifest`].
+//! Tables of `bin.<name>` are merged into it for the binary, replacing values of same keys.
+//!
+//! ```toml
+//! [package.metadata.sxs-manifest]
+//! compatibility.supported_os = ["Windows10"]
+//! compatibility.max_version_tested = ["10.0.19041.0"]
+//! windows_settings.dpi_awareness = ["permonitorv2"]
+//!
+//! [package.metadata.sxs-manifest.bin.installer]
+//! trust_info.requested_execution_level.level = "requireAdministrator"
+//! ```
+//!
+//! `assembly_identity.name` and `assembly_identity.version` default to the name and version of the package.
+//!
+//! ```no_run
+//! // in build.rs
+//! let manifest = sxs_manifest::metadata::load(Some("installer")).unwrap();
+//! let manifest = manifest.serialize_to_string().unwrap();
+//! ```
+use crate::manifest::{AssemblyManifest, AssemblyVersion};
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// Key of table in `package.metadata`
+pub const METADATA_KEY: &str = "sxs-manifest";
+
+/// Error of loading manifest from `Cargo.toml`
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataError {
+    /// Environment variable set by Cargo is missing
+    #[error("environment variable {0} is not set")]
+    MissingEnv(&'static str),
+    /// `Cargo.toml` can't be read
+    #[error("failed to read {} - {source}", path.display())]
+    Io {
+        #[allow(missing_docs)]
+        path: PathBuf,
+        #[allow(missing_docs)]
+        source: std::io::Error,
+    },
+    /// `Cargo.toml` is not valid TOML
+    #[error("invalid Cargo.toml - {0}")]
+    Toml(#[from] toml::de::Error),
+    /// Setting is invalid
+    #[error("{key} - {message}")]
+    Invalid {
+        /// Dotted key, such as `package.metadata.sxs-manifest.compatibility.supported_os[0]`
+        key: String,
+        #[allow(missing_docs)]
+        message: String,
+    },
+}
+
+/// Package which provides defaults of `assembly_identity`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PackageInfo {
+    #[allow(missing_docs)]
+    pub name: String,
+    /// Semantic version, converted with [`AssemblyVersion::from_semver`](../manifest/struct.AssemblyVersion.html#method.from_semver)
+    pub version: String,
+}
+
+impl PackageInfo {
+    /// Current package, from `CARGO_PKG_NAME` and `CARGO_PKG_VERSION`.
+    pub fn from_env() -> Result<Self, MetadataError> {
+        Ok(PackageInfo {
+            name: env("CARGO_PKG_NAME")?,
+            version: env("CARGO_PKG_VERSION")?,
+        })
+    }
+}
+
+fn env(name: &'static str) -> Result<String, MetadataError> {
+    std::env::var(name).map_err(|_| MetadataError::MissingEnv(name))
+}
+
+/// Load manifest of `bin` from `Cargo.toml` of current package.
+///
+/// Cargo sets `CARGO_MANIFEST_DIR` and package variables while running build scripts.
+pub fn load(bin: Option<&str>) -> Result<AssemblyManifest, MetadataError> {
+    let path = PathBuf::from(env("CARGO_MANIFEST_DIR")?).join("Cargo.toml");
+    let cargo_toml =
+        std::fs::read_to_string(&path).map_err(|source| MetadataError::Io { path, source })?;
+    parse(&cargo_toml, bin, &PackageInfo::from_env()?)
+}
+
+/// Build manifest of `bin` from contents of `Cargo.toml`.
+///
+/// Package without metadata table results in default manifest with identity of `package`.
+/// Unknown keys are errors, to catch misspelled settings.
+pub fn parse(
+    cargo_toml: &str,
+    bin: Option<&str>,
+    package: &PackageInfo,
+) -> Result<AssemblyManifest, MetadataError> {
+    let base_key = format!("package.metadata.{}", METADATA_KEY);
+    let cargo_toml = cargo_toml.parse::<Table>()?;
+    let metadata = ["package", "metadata", METADATA_KEY].iter().try_fold(
+        Some(&cargo_toml),
+        |table, key| match table.and_then(|table| table.get(*key)) {
+            None => Ok(None),
+            Some(Value::Table(table)) => Ok(Some(table)),
+            Some(_) => Err(invalid(&base_key, "must be a table")),
+        },
+    )?;
+
+    let mut settings = metadata.cloned().unwrap_or_default();
+    let bins = settings.remove("bin");
+    let overrides = match (bins, bin) {
+        (Some(Value::Table(mut bins)), Some(bin)) => match bins.remove(bin) {
+            Some(Value::Table(overrides)) => Some(overrides),
+            Some(_) => {
+                return Err(invalid(
+                    &format!("{}.bin.{}", base_key, bin),
+                    "must be a table",
+                ))
+            }
+            None => None,
+        },
+        (Some(Value::Table(_)), None) | (None, _) => None,
+        (Some(_), _) => return Err(invalid(&format!("{}.bin", base_key), "must be a table")),
+    };
+    if let Some(overrides) = overrides.clone() {
+        merge(&mut settings, overrides);
+    }
+
+    let key_of = |path: &[Segment]| {
+        let from_override = overrides
+            .as_ref()
+            .is_some_and(|overrides| lookup(overrides, path));
+        let mut key = if from_override {
+            format!("{}.bin.{}", base_key, bin.unwrap_or_default())
+        } else {
+            base_key.clone()
+        };
+        for segment in path {
+            match segment {
+                Segment::Key(name) => {
+                    key.push('.');
+                    key.push_str(name);
+                }
+                Segment::Index(index) => key.push_str(&format!("[{}]", index)),
+            }
+        }
+        key
+    };
+
+    fill_identity(&mut settings, package, &key_of)?;
+
+    let mut unknown = None;
+    let mut callback = |path: serde_ignored::Path<'_>| {
+        if unknown.is_none() {
+            let mut segments = Vec::new();
+            ignored_segments(&path, &mut segments);
+            unknown = Some(segments);
+        }
+    };
+    let manifest = serde_path_to_error::deserialize::<_, AssemblyManifest>(
+        serde_ignored::Deserializer::new(Value::Table(settings), &mut callback),
+    )
+    .map_err(|err| {
+        let path = err
+            .path()
+            .iter()
+            .filter_map(|segment| match segment {
+                serde_path_to_error::Segment::Map { key } => Some(Segment::Key(key.clone())),
+                serde_path_to_error::Segment::Seq { index } => Some(Segment::Index(*index)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        invalid(&key_of(&path), err.inner().to_string())
+    })?;
+    if let Some(path) = unknown {
+        return Err(invalid(&key_of(&path), "unknown key"));
+    }
+    Ok(manifest)
+}
+
+fn invalid<S: Into<String>>(key: &str, message: S) -> MetadataError {
+    MetadataError::Invalid {
+        key: key.to_string(),
+        message: message.into(),
+    }
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn ignored_segments(path: &serde_ignored::Path<'_>, segments: &mut Vec<Segment>) {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => {}
+        Path::Seq { parent, index } => {
+            ignored_segments(parent, segments);
+            segments.push(Segment::Index(*index));
+        }
+        Path::Map { parent, key } => {
+            ignored_segments(parent, segments);
+            segments.push(Segment::Key(key.clone()));
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => ignored_segments(parent, segments),
+    }
+}
+
+/// Whether value at `path` is in `table`
+fn lookup(table: &Table, path: &[Segment]) -> bool {
+    let mut value = match path.first() {
+        Some(Segment::Key(key)) => match table.get(key) {
+            Some(value) => value,
+            None => return false,
+        },
+        _ => return false,
+    };
+    for segment in &path[1..] {
+        let child = match (segment, value) {
+            (Segment::Key(key), Value::Table(table)) => table.get(key),
+            (Segment::Index(index), Value::Array(array)) => array.get(*index),
+            _ => None,
+        };
+        value = match child {
+            Some(child) => child,
+            None => return false,
+        };
+    }
+    true
+}
+
+/// Merge tables recursively, and replace other values
+fn merge(base: &mut Table, overrides: Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn fill_identity(
+    settings: &mut Table,
+    package: &PackageInfo,
+    key_of: &dyn Fn(&[Segment]) -> String,
+) -> Result<(), MetadataError> {
+    let identity_path = [Segment::Key("assembly_identity".to_string())];
+    let identity = match settings
+        .entry("assembly_identity")
+        .or_insert_with(|| Value::Table(Table::new()))
+    {
+        Value::Table(identity) => identity,
+        _ => return Err(invalid(&key_of(&identity_path), "must be a table")),
+    };
+    if !identity.contains_key("name") {
+        identity.insert("name".to_string(), Value::String(package.name.clone()));
+    }
+    if !identity.contains_key("version") {
+        let version = AssemblyVersion::from_semver(&package.version)
+            .map_err(|err| invalid("package.version", err.to_string()))?;
+        identity.insert("version".to_string(), Value::String(version.to_string()));
+    }
+    Ok(())
+}
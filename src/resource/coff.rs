@@ -0,0 +1,375 @@
+use super::{ResFile, Resource, ResourceName};
+use crate::manifest::ProcessArchitecture;
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+/// Machine type of COFF object
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Machine {
+    /// `IMAGE_FILE_MACHINE_I386`
+    X86,
+    /// `IMAGE_FILE_MACHINE_AMD64`
+    X64,
+    /// `IMAGE_FILE_MACHINE_ARMNT`
+    Arm,
+    /// `IMAGE_FILE_MACHINE_ARM64`
+    Arm64,
+}
+
+impl Machine {
+    /// Machine of process architecture, such as [`ProcessArchitecture::from_cargo_env`]
+    pub fn from_architecture(architecture: ProcessArchitecture) -> Option<Self> {
+        match architecture {
+            ProcessArchitecture::X86 => Some(Machine::X86),
+            ProcessArchitecture::X86_64 => Some(Machine::X64),
+            ProcessArchitecture::Arm => Some(Machine::Arm),
+            ProcessArchitecture::Arm64 => Some(Machine::Arm64),
+            _ => None,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub const fn value(self) -> u16 {
+        match self {
+            Machine::X86 => 0x14c,
+            Machine::X64 => 0x8664,
+            Machine::Arm => 0x1c4,
+            Machine::Arm64 => 0xaa64,
+        }
+    }
+
+    /// Relocation to image relative address
+    const fn addr32nb(self) -> u16 {
+        match self {
+            Machine::X86 => 7,
+            Machine::X64 => 3,
+            Machine::Arm | Machine::Arm64 => 2,
+        }
+    }
+
+    const fn is_32bit(self) -> bool {
+        matches!(self, Machine::X86 | Machine::Arm)
+    }
+}
+
+const FILE_HEADER_SIZE: usize = 20;
+const SECTION_HEADER_SIZE: usize = 40;
+const RELOCATION_SIZE: usize = 10;
+const IMAGE_FILE_32BIT_MACHINE: u16 = 0x0100;
+const IMAGE_SCN_CNT_INITIALIZED_DATA: u32 = 0x0000_0040;
+const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+const IMAGE_SYM_ABSOLUTE: i16 = -1;
+const IMAGE_SYM_CLASS_STATIC: u8 = 3;
+const SUBDIRECTORY: u32 = 0x8000_0000;
+/// `@feat.00`, `.rsrc$01` and `.rsrc$02` with their auxiliary records
+const FIRST_DATA_SYMBOL: u32 = 5;
+
+/// Entry of directory, with named ones ordered first as loader requires
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+enum Key {
+    Name(Vec<u16>),
+    Id(u16),
+}
+
+impl From<&ResourceName> for Key {
+    fn from(name: &ResourceName) -> Self {
+        match name {
+            ResourceName::Id(id) => Key::Id(*id),
+            ResourceName::Name(name) => Key::Name(name.encode_utf16().collect()),
+        }
+    }
+}
+
+type Tree<'a> = BTreeMap<Key, BTreeMap<Key, BTreeMap<u16, &'a Resource>>>;
+
+/// Resource directory of `.rsrc$01`, and offsets of its data entries
+struct Directory {
+    bytes: Vec<u8>,
+    data_entry_offsets: Vec<usize>,
+}
+
+fn directory_size(entries: usize) -> usize {
+    16 + 8 * entries
+}
+
+fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_directory_header(bytes: &mut Vec<u8>, named: usize, ids: usize) {
+    // characteristics, time stamp, major and minor version
+    bytes.extend_from_slice(&[0; 12]);
+    push_u16(bytes, named as u16);
+    push_u16(bytes, ids as u16);
+}
+
+fn is_named(key: &Key) -> bool {
+    matches!(key, Key::Name(_))
+}
+
+/// Build directory tree of type, name and language.
+///
+/// Tables are laid out in breadth-first order, followed by data entries and name strings.
+fn build_directory(tree: &Tree<'_>) -> Directory {
+    let types_size = tree
+        .values()
+        .map(|names| directory_size(names.len()))
+        .sum::<usize>();
+    let names_size = tree
+        .values()
+        .flat_map(|names| names.values())
+        .map(|languages| directory_size(languages.len()))
+        .sum::<usize>();
+    let resources = || {
+        tree.values()
+            .flat_map(|names| names.values())
+            .flat_map(|languages| languages.values())
+    };
+    let resource_count = resources().count();
+    let data_entries_start = directory_size(tree.len()) + types_size + names_size;
+    let strings_start = data_entries_start + 16 * resource_count;
+
+    let mut strings = Vec::new();
+    let mut string_offsets = BTreeMap::new();
+    let mut key_value = |key: &Key| match key {
+        Key::Id(id) => u32::from(*id),
+        Key::Name(units) => {
+            let offset = *string_offsets.entry(units.clone()).or_insert_with(|| {
+                let offset = strings_start + strings.len();
+                push_u16(&mut strings, units.len() as u16);
+                for unit in units {
+                    push_u16(&mut strings, *unit);
+                }
+                offset
+            });
+            offset as u32 | SUBDIRECTORY
+        }
+    };
+
+    let mut bytes = Vec::with_capacity(strings_start);
+    let named = tree.keys().filter(|key| is_named(key)).count();
+    push_directory_header(&mut bytes, named, tree.len() - named);
+    let mut next_table = directory_size(tree.len());
+    for (r#type, names) in tree {
+        push_u32(&mut bytes, key_value(r#type));
+        push_u32(&mut bytes, next_table as u32 | SUBDIRECTORY);
+        next_table += directory_size(names.len());
+    }
+    for names in tree.values() {
+        let named = names.keys().filter(|key| is_named(key)).count();
+        push_directory_header(&mut bytes, named, names.len() - named);
+        for (name, languages) in names {
+            push_u32(&mut bytes, key_value(name));
+            push_u32(&mut bytes, next_table as u32 | SUBDIRECTORY);
+            next_table += directory_size(languages.len());
+        }
+    }
+    let mut data_entry = data_entries_start;
+    for languages in tree.values().flat_map(|names| names.values()) {
+        push_directory_header(&mut bytes, 0, languages.len());
+        for language in languages.keys() {
+            push_u32(&mut bytes, u32::from(*language));
+            push_u32(&mut bytes, data_entry as u32);
+            data_entry += 16;
+        }
+    }
+
+    let mut data_entry_offsets = Vec::with_capacity(resource_count);
+    for resource in resources() {
+        data_entry_offsets.push(bytes.len());
+        // RVA of data, filled by linker with relocation to symbol of data
+        push_u32(&mut bytes, 0);
+        push_u32(&mut bytes, resource.data.len() as u32);
+        // code page and reserved
+        bytes.extend_from_slice(&[0; 8]);
+    }
+    bytes.extend_from_slice(&strings);
+    bytes.resize(bytes.len() + (4 - bytes.len() % 4) % 4, 0);
+
+    Directory {
+        bytes,
+        data_entry_offsets,
+    }
+}
+
+fn push_section_header(
+    bytes: &mut Vec<u8>,
+    name: &[u8; 8],
+    size: usize,
+    pointer: usize,
+    relocations: usize,
+    relocation_count: usize,
+) {
+    bytes.extend_from_slice(name);
+    // virtual size and address
+    bytes.extend_from_slice(&[0; 8]);
+    push_u32(bytes, size as u32);
+    push_u32(bytes, pointer as u32);
+    push_u32(bytes, relocations as u32);
+    // line numbers
+    push_u32(bytes, 0);
+    push_u16(bytes, relocation_count as u16);
+    push_u16(bytes, 0);
+    push_u32(bytes, IMAGE_SCN_CNT_INITIALIZED_DATA | IMAGE_SCN_MEM_READ);
+}
+
+fn push_symbol(
+    bytes: &mut Vec<u8>,
+    name: &[u8],
+    value: u32,
+    section: i16,
+    storage_class: u8,
+    aux: u8,
+) {
+    let mut short_name = [0; 8];
+    short_name[..name.len()].copy_from_slice(name);
+    bytes.extend_from_slice(&short_name);
+    push_u32(bytes, value);
+    push_u16(bytes, section as u16);
+    // type
+    push_u16(bytes, 0);
+    bytes.push(storage_class);
+    bytes.push(aux);
+}
+
+fn push_section_aux(bytes: &mut Vec<u8>, size: usize, relocation_count: usize, number: u16) {
+    push_u32(bytes, size as u32);
+    push_u16(bytes, relocation_count as u16);
+    // line numbers and checksum
+    bytes.extend_from_slice(&[0; 6]);
+    push_u16(bytes, number);
+    // selection and padding
+    bytes.extend_from_slice(&[0; 4]);
+}
+
+/// COFF object, which can be passed to linkers
+impl ResFile {
+    /// Write COFF object holding resources, in `.rsrc$01` for directory and `.rsrc$02` for data.
+    ///
+    /// Linkers merge them into `.rsrc`, so link.exe and lld-link can link it without resource compiler.
+    /// Resources with same type, name and language are written once, with the last one.
+    ///
+    /// ```no_run
+    /// // in build.rs
+    /// use sxs_manifest::{manifest::ProcessArchitecture, resource::*, AssemblyManifest};
+    ///
+    /// let manifest = AssemblyManifest::default();
+    /// let machine = ProcessArchitecture::from_cargo_env()
+    ///     .and_then(Machine::from_architecture)
+    ///     .unwrap();
+    /// let mut res = ResFile::default();
+    /// res.insert(Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::NEUTRAL).unwrap());
+    ///
+    /// let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("manifest.obj");
+    /// res.write_coff(machine, std::fs::File::create(&path).unwrap()).unwrap();
+    /// println!("cargo:rustc-link-arg={}", path.display());
+    /// ```
+    pub fn write_coff<W: Write>(&self, machine: Machine, mut writer: W) -> io::Result<W> {
+        let mut tree = Tree::new();
+        for resource in &self.resources {
+            tree.entry(Key::from(&resource.r#type))
+                .or_default()
+                .entry(Key::from(&resource.name))
+                .or_default()
+                .insert(resource.language.0, resource);
+        }
+        let resources = tree
+            .values()
+            .flat_map(|names| names.values())
+            .flat_map(|languages| languages.values())
+            .collect::<Vec<_>>();
+        if resources.len() > usize::from(u16::MAX) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too many resources for COFF object",
+            ));
+        }
+
+        let mut data = Vec::new();
+        let mut data_offsets = Vec::with_capacity(resources.len());
+        for resource in &resources {
+            data_offsets.push(data.len());
+            data.extend_from_slice(&resource.data);
+            data.resize(data.len() + (8 - data.len() % 8) % 8, 0);
+        }
+        let directory = build_directory(&tree);
+
+        let directory_pointer = FILE_HEADER_SIZE + 2 * SECTION_HEADER_SIZE;
+        let relocations_pointer = directory_pointer + directory.bytes.len();
+        let data_pointer = relocations_pointer + RELOCATION_SIZE * resources.len();
+        let symbols_pointer = data_pointer + data.len();
+        let symbol_count = FIRST_DATA_SYMBOL as usize + resources.len();
+
+        let mut bytes = Vec::with_capacity(symbols_pointer + 18 * symbol_count + 4);
+        push_u16(&mut bytes, machine.value());
+        push_u16(&mut bytes, 2);
+        // time stamp, for reproducible builds
+        push_u32(&mut bytes, 0);
+        push_u32(&mut bytes, symbols_pointer as u32);
+        push_u32(&mut bytes, symbol_count as u32);
+        // optional header
+        push_u16(&mut bytes, 0);
+        push_u16(
+            &mut bytes,
+            if machine.is_32bit() {
+                IMAGE_FILE_32BIT_MACHINE
+            } else {
+                0
+            },
+        );
+
+        push_section_header(
+            &mut bytes,
+            b".rsrc$01",
+            directory.bytes.len(),
+            directory_pointer,
+            relocations_pointer,
+            resources.len(),
+        );
+        push_section_header(&mut bytes, b".rsrc$02", data.len(), data_pointer, 0, 0);
+
+        bytes.extend_from_slice(&directory.bytes);
+        for (index, offset) in directory.data_entry_offsets.iter().enumerate() {
+            push_u32(&mut bytes, *offset as u32);
+            push_u32(&mut bytes, FIRST_DATA_SYMBOL + index as u32);
+            push_u16(&mut bytes, machine.addr32nb());
+        }
+        bytes.extend_from_slice(&data);
+
+        // compatible with /SAFESEH
+        push_symbol(
+            &mut bytes,
+            b"@feat.00",
+            0x11,
+            IMAGE_SYM_ABSOLUTE,
+            IMAGE_SYM_CLASS_STATIC,
+            0,
+        );
+        push_symbol(&mut bytes, b".rsrc$01", 0, 1, IMAGE_SYM_CLASS_STATIC, 1);
+        push_section_aux(&mut bytes, directory.bytes.len(), resources.len(), 0);
+        push_symbol(&mut bytes, b".rsrc$02", 0, 2, IMAGE_SYM_CLASS_STATIC, 1);
+        push_section_aux(&mut bytes, data.len(), 0, 0);
+        for (index, offset) in data_offsets.iter().enumerate() {
+            let name = format!("$R{:06x}", index);
+            push_symbol(
+                &mut bytes,
+                name.as_bytes(),
+                *offset as u32,
+                2,
+                IMAGE_SYM_CLASS_STATIC,
+                0,
+            );
+        }
+        // empty string table
+        push_u32(&mut bytes, 4);
+
+        writer.write_all(&bytes)?;
+        Ok(writer)
+    }
+}
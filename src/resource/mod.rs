@@ -0,0 +1,63 @@
+//! Manifest as Win32 resource
+//!
+//! Manifest is stored as `RT_MANIFEST` resource, whose ID tells loader how it is used.
+//! It can be compiled with [`ResourceScript`] and resource compilers,
+//! or written without them as `.res` file or COFF object with [`ResFile`].
+mod coff;
+pub use coff::*;
+mod rc;
+pub use rc::*;
+mod res;
+pub use res::*;
+
+/// Resource type of manifest
+pub const RT_MANIFEST: u16 = 24;
+
+/// Kind of module, which decides resource ID of manifest
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum ManifestKind {
+    /// `CREATEPROCESS_MANIFEST_RESOURCE_ID`, used for process creation of EXE
+    #[default]
+    Executable,
+    /// `ISOLATIONAWARE_MANIFEST_RESOURCE_ID`, used for DLL loaded with `ISOLATION_AWARE_ENABLED`
+    IsolationAwareDll,
+    /// `ISOLATIONAWARE_NOSTATICIMPORT_MANIFEST_RESOURCE_ID`, used only for dynamically loaded DLLs
+    Other,
+}
+
+impl ManifestKind {
+    /// `1`, `2` or `3`
+    pub const fn resource_id(self) -> u16 {
+        match self {
+            ManifestKind::Executable => 1,
+            ManifestKind::IsolationAwareDll => 2,
+            ManifestKind::Other => 3,
+        }
+    }
+}
+
+/// Language of resource, `LANGID` made of primary and sub language
+#[derive(Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Copy, Clone)]
+pub struct LanguageId(pub u16);
+
+impl LanguageId {
+    /// `LANG_NEUTRAL`, `SUBLANG_NEUTRAL`
+    pub const NEUTRAL: LanguageId = LanguageId(0);
+    /// `LANG_ENGLISH`, `SUBLANG_ENGLISH_US`
+    pub const EN_US: LanguageId = LanguageId(0x0409);
+
+    /// `MAKELANGID`
+    pub const fn new(primary: u16, sub: u16) -> Self {
+        LanguageId((sub << 10) | primary)
+    }
+
+    #[allow(missing_docs)]
+    pub const fn primary(self) -> u16 {
+        self.0 & 0x3ff
+    }
+
+    #[allow(missing_docs)]
+    pub const fn sub(self) -> u16 {
+        self.0 >> 10
+    }
+}
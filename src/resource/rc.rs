@@ -0,0 +1,69 @@
+use super::{LanguageId, ManifestKind, RT_MANIFEST};
+use std::{
+    fmt::{Display, Formatter},
+    path::PathBuf,
+};
+
+/// `.rc` script including manifest file as `RT_MANIFEST` resource,
+/// for rc.exe, windres and llvm-rc
+///
+/// ```
+/// use sxs_manifest::resource::*;
+///
+/// let mut script = ResourceScript::new("app.manifest");
+/// script.kind(ManifestKind::IsolationAwareDll);
+/// assert_eq!(script.to_string(), "LANGUAGE 0x0, 0x0\n2 24 \"app.manifest\"\n");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ResourceScript {
+    manifest_path: PathBuf,
+    kind: ManifestKind,
+    language: LanguageId,
+}
+
+impl ResourceScript {
+    /// Script for executable, with neutral language
+    pub fn new<P: Into<PathBuf>>(manifest_path: P) -> Self {
+        ResourceScript {
+            manifest_path: manifest_path.into(),
+            kind: ManifestKind::default(),
+            language: LanguageId::NEUTRAL,
+        }
+    }
+
+    #[allow(missing_docs)]
+    pub fn kind(&mut self, kind: ManifestKind) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    #[allow(missing_docs)]
+    pub fn language(&mut self, language: LanguageId) -> &mut Self {
+        self.language = language;
+        self
+    }
+}
+
+/// Path is quoted with backslashes doubled.
+/// Script is declared as UTF-8 when path is not ASCII.
+impl Display for ResourceScript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = self.manifest_path.to_string_lossy();
+        if !path.is_ascii() {
+            writeln!(f, "#pragma code_page(65001)")?;
+        }
+        writeln!(
+            f,
+            "LANGUAGE {:#x}, {:#x}",
+            self.language.primary(),
+            self.language.sub()
+        )?;
+        writeln!(
+            f,
+            "{} {} \"{}\"",
+            self.kind.resource_id(),
+            RT_MANIFEST,
+            path.replace('\\', "\\\\").replace('"', "\"\"")
+        )
+    }
+}
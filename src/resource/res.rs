@@ -0,0 +1,288 @@
+use super::{LanguageId, ManifestKind, RT_MANIFEST};
+use crate::{error::SerializeResult, AssemblyManifest};
+use std::io::{self, Read, Write};
+
+/// Error of reading `.res` file
+#[derive(Debug, thiserror::Error)]
+pub enum ResError {
+    #[allow(missing_docs)]
+    #[error("failed to read resource file - {0}")]
+    Io(#[from] io::Error),
+    /// Resource file is malformed
+    #[error("invalid resource file at offset {offset} - {detail}")]
+    Invalid {
+        #[allow(missing_docs)]
+        offset: usize,
+        #[allow(missing_docs)]
+        detail: &'static str,
+    },
+}
+
+/// Type or name of resource
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+pub enum ResourceName {
+    /// Ordinal, such as [`RT_MANIFEST`]
+    Id(u16),
+    /// Name, which is usually upper case
+    Name(String),
+}
+
+/// Resource entry of `.res` file
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Resource {
+    #[allow(missing_docs)]
+    pub r#type: ResourceName,
+    #[allow(missing_docs)]
+    pub name: ResourceName,
+    #[allow(missing_docs)]
+    pub language: LanguageId,
+    /// `MOVEABLE`, `PURE` and `DISCARDABLE` flags, which are ignored by Win32
+    pub memory_flags: u16,
+    #[allow(missing_docs)]
+    pub data_version: u32,
+    /// User defined version
+    pub version: u32,
+    /// User defined characteristics
+    pub characteristics: u32,
+    #[allow(missing_docs)]
+    pub data: Vec<u8>,
+}
+
+impl Resource {
+    /// `MOVEABLE | PURE`, which resource compilers set for manifests
+    pub const DEFAULT_MEMORY_FLAGS: u16 = 0x0030;
+
+    /// Resource with default flags and versions
+    pub fn new(
+        r#type: ResourceName,
+        name: ResourceName,
+        language: LanguageId,
+        data: Vec<u8>,
+    ) -> Self {
+        Resource {
+            r#type,
+            name,
+            language,
+            memory_flags: Resource::DEFAULT_MEMORY_FLAGS,
+            data_version: 0,
+            version: 0,
+            characteristics: 0,
+            data,
+        }
+    }
+
+    /// `RT_MANIFEST` resource of serialized `manifest`, with ID of `kind`
+    pub fn manifest(
+        manifest: &AssemblyManifest,
+        kind: ManifestKind,
+        language: LanguageId,
+    ) -> SerializeResult<Self> {
+        Ok(Resource::new(
+            ResourceName::Id(RT_MANIFEST),
+            ResourceName::Id(kind.resource_id()),
+            language,
+            manifest.serialize(Vec::new())?,
+        ))
+    }
+
+    fn is_null(&self) -> bool {
+        self.r#type == ResourceName::Id(0)
+            && self.name == ResourceName::Id(0)
+            && self.data.is_empty()
+    }
+}
+
+/// Compiled resource file, `.res`
+///
+/// ```
+/// use sxs_manifest::{resource::*, AssemblyManifest};
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let manifest = AssemblyManifest::default();
+/// let mut res = ResFile::default();
+/// res.insert(Resource::manifest(&manifest, ManifestKind::Executable, LanguageId::NEUTRAL)?);
+/// let bytes = res.write(Vec::new())?;
+/// assert_eq!(ResFile::read(bytes.as_slice())?, res);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct ResFile {
+    #[allow(missing_docs)]
+    pub resources: Vec<Resource>,
+}
+
+impl ResFile {
+    /// Read resources, such as output of rc.exe, to merge manifest into.
+    ///
+    /// Leading null entry is not included in [`ResFile::resources`].
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, ResError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let mut resources = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let resource = read_resource(&bytes, &mut offset)?;
+            if !resource.is_null() {
+                resources.push(resource);
+            }
+        }
+        Ok(ResFile { resources })
+    }
+
+    /// Add resource, replacing one with same type, name and language.
+    ///
+    /// Manifest replaces every `RT_MANIFEST` with the same ID regardless of language,
+    /// such as one at `0x409` written by rc.exe, since the loader picks only one of them.
+    pub fn insert(&mut self, resource: Resource) {
+        let is_manifest = resource.r#type == ResourceName::Id(RT_MANIFEST);
+        let replaces = |existing: &Resource| {
+            existing.r#type == resource.r#type
+                && existing.name == resource.name
+                && (is_manifest || existing.language == resource.language)
+        };
+        match self.resources.iter().position(replaces) {
+            Some(idx) => {
+                let rest = self.resources.split_off(idx + 1);
+                self.resources
+                    .extend(rest.into_iter().filter(|existing| !replaces(existing)));
+                self.resources[idx] = resource;
+            }
+            None => self.resources.push(resource),
+        }
+    }
+
+    /// Write resources after null entry, which marks 32-bit resource file
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<W> {
+        let null = Resource {
+            memory_flags: 0,
+            ..Resource::new(
+                ResourceName::Id(0),
+                ResourceName::Id(0),
+                LanguageId::NEUTRAL,
+                Vec::new(),
+            )
+        };
+        for resource in std::iter::once(&null).chain(&self.resources) {
+            writer.write_all(&resource_bytes(resource))?;
+        }
+        Ok(writer)
+    }
+}
+
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+fn name_bytes(name: &ResourceName, bytes: &mut Vec<u8>) {
+    match name {
+        ResourceName::Id(id) => {
+            bytes.extend_from_slice(&0xffffu16.to_le_bytes());
+            bytes.extend_from_slice(&id.to_le_bytes());
+        }
+        ResourceName::Name(name) => {
+            for unit in name.encode_utf16().chain(std::iter::once(0)) {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+    }
+}
+
+fn resource_bytes(resource: &Resource) -> Vec<u8> {
+    let mut names = Vec::new();
+    name_bytes(&resource.r#type, &mut names);
+    name_bytes(&resource.name, &mut names);
+    names.resize(names.len() + padding(names.len()), 0);
+    let header_size = 8 + names.len() + 16;
+
+    let mut bytes = Vec::with_capacity(header_size + resource.data.len() + 3);
+    bytes.extend_from_slice(&(resource.data.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&(header_size as u32).to_le_bytes());
+    bytes.extend_from_slice(&names);
+    bytes.extend_from_slice(&resource.data_version.to_le_bytes());
+    bytes.extend_from_slice(&resource.memory_flags.to_le_bytes());
+    bytes.extend_from_slice(&resource.language.0.to_le_bytes());
+    bytes.extend_from_slice(&resource.version.to_le_bytes());
+    bytes.extend_from_slice(&resource.characteristics.to_le_bytes());
+    bytes.extend_from_slice(&resource.data);
+    bytes.resize(bytes.len() + padding(resource.data.len()), 0);
+    bytes
+}
+
+fn invalid(offset: usize, detail: &'static str) -> ResError {
+    ResError::Invalid { offset, detail }
+}
+
+fn slice(bytes: &[u8], offset: usize, len: usize) -> Result<&[u8], ResError> {
+    bytes
+        .get(offset..offset.saturating_add(len))
+        .ok_or_else(|| invalid(offset, "unexpected end of file"))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, ResError> {
+    let slice = slice(bytes, offset, 2)?;
+    Ok(u16::from_le_bytes([slice[0], slice[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, ResError> {
+    let slice = slice(bytes, offset, 4)?;
+    Ok(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+fn read_name(header: &[u8], offset: &mut usize, base: usize) -> Result<ResourceName, ResError> {
+    if u16_at(header, *offset).map_err(|_| invalid(base + *offset, "truncated header"))? == 0xffff {
+        let id =
+            u16_at(header, *offset + 2).map_err(|_| invalid(base + *offset, "truncated header"))?;
+        *offset += 4;
+        return Ok(ResourceName::Id(id));
+    }
+
+    let start = *offset;
+    let mut units = Vec::new();
+    loop {
+        let unit =
+            u16_at(header, *offset).map_err(|_| invalid(base + start, "unterminated name"))?;
+        *offset += 2;
+        if unit == 0 {
+            break;
+        }
+        units.push(unit);
+    }
+    String::from_utf16(&units)
+        .map(ResourceName::Name)
+        .map_err(|_| invalid(base + start, "name is not UTF-16"))
+}
+
+fn read_resource(bytes: &[u8], offset: &mut usize) -> Result<Resource, ResError> {
+    let start = *offset;
+    let data_size = u32_at(bytes, start)? as usize;
+    let header_size = u32_at(bytes, start + 4)? as usize;
+    if header_size < 8 + 8 + 16 {
+        return Err(invalid(start + 4, "header is too small"));
+    }
+    let header = slice(bytes, start, header_size)?;
+
+    let mut names_end = 8;
+    let r#type = read_name(header, &mut names_end, start)?;
+    let name = read_name(header, &mut names_end, start)?;
+    names_end += padding(names_end);
+    if names_end + 16 > header_size {
+        return Err(invalid(start + 4, "header is too small"));
+    }
+
+    let data_start = start + header_size;
+    let resource = Resource {
+        r#type,
+        name,
+        data_version: u32_at(header, names_end)?,
+        memory_flags: u16_at(header, names_end + 4)?,
+        language: LanguageId(u16_at(header, names_end + 6)?),
+        version: u32_at(header, names_end + 8)?,
+        characteristics: u32_at(header, names_end + 12)?,
+        data: slice(bytes, data_start, data_size)?.to_vec(),
+    };
+    let end = data_start + data_size;
+    *offset = (end + padding(end)).min(bytes.len());
+    Ok(resource)
+}
@@ -0,0 +1,603 @@
+//! Validation of XML manifests against XDR schema
+//!
+//! [`Schema::bundled`] is the schema of `manifest.schema.xml`, which ships with this crate.
+//! Elements and attributes of namespaces other than the schema's own are extensions,
+//! so they are not checked.
+use crate::{
+    debug::Path,
+    deserialize::{Element, ParseResult},
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{Display, Formatter},
+    io::Read,
+};
+
+const NS_XML_DATA: &str = "urn:schemas-microsoft-com:xml-data";
+const NS_DATATYPES: &str = "urn:schemas-microsoft-com:datatypes";
+
+/// Violation of schema found by [`Schema::validate`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SchemaViolation {
+    /// 1-based line number of the element
+    pub line: u64,
+    /// 1-based column number of the element
+    pub column: u64,
+    /// Local name of the element
+    pub element: String,
+    #[allow(missing_docs)]
+    pub detail: String,
+}
+
+impl Display for SchemaViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} <{}> {}",
+            self.line, self.column, self.element, self.detail
+        )
+    }
+}
+
+/// `dt:type` of attribute or element
+#[derive(Debug, Clone)]
+enum DataType {
+    /// Values of `dt:values`.
+    ///
+    /// Comma-separated list is accepted, as typelib `flags` is written.
+    Enumeration(Vec<String>),
+    BinHex,
+    Boolean,
+    Unsigned(u64),
+    Signed(i64, i64),
+    /// `string` and types which aren't checked
+    Any,
+}
+
+impl DataType {
+    fn from_element(element: &Element) -> DataType {
+        let values = || {
+            namespaced_attribute(element, NS_DATATYPES, "values")
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(str::to_string)
+                .collect()
+        };
+        match namespaced_attribute(element, NS_DATATYPES, "type") {
+            Some("enumeration") => DataType::Enumeration(values()),
+            Some("bin.hex") => DataType::BinHex,
+            Some("boolean") => DataType::Boolean,
+            Some("ui1") => DataType::Unsigned(u8::MAX as u64),
+            Some("ui2") => DataType::Unsigned(u16::MAX as u64),
+            Some("ui4") => DataType::Unsigned(u32::MAX as u64),
+            Some("ui8") => DataType::Unsigned(u64::MAX),
+            Some("i1") => DataType::Signed(i8::MIN as i64, i8::MAX as i64),
+            Some("i2") => DataType::Signed(i16::MIN as i64, i16::MAX as i64),
+            Some("i4") | Some("int") => DataType::Signed(i32::MIN as i64, i32::MAX as i64),
+            Some("i8") => DataType::Signed(i64::MIN, i64::MAX),
+            _ => DataType::Any,
+        }
+    }
+
+    /// Reason of mismatch
+    fn check(&self, value: &str) -> Option<String> {
+        let valid = match self {
+            DataType::Enumeration(values) => {
+                return value
+                    .split(',')
+                    .map(str::trim)
+                    .find(|item| !values.iter().any(|value| value == item))
+                    .map(|item| format!("{} is not one of {}", item, values.join(", ")));
+            }
+            DataType::BinHex => {
+                value.len() % 2 == 0 && value.chars().all(|c| c.is_ascii_hexdigit())
+            }
+            DataType::Boolean => value == "0" || value == "1",
+            DataType::Unsigned(max) => matches!(value.parse::<u64>(), Ok(value) if value <= *max),
+            DataType::Signed(min, max) => {
+                matches!(value.parse::<i64>(), Ok(value) if *min <= value && value <= *max)
+            }
+            DataType::Any => true,
+        };
+        if valid {
+            None
+        } else {
+            Some(format!("{} is not {}", value, self.name()))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DataType::Enumeration(_) => "enumeration",
+            DataType::BinHex => "bin.hex",
+            DataType::Boolean => "boolean",
+            DataType::Unsigned(_) => "unsigned integer",
+            DataType::Signed(_, _) => "integer",
+            DataType::Any => "string",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Content {
+    Empty,
+    TextOnly,
+    EltOnly,
+    Mixed,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Order {
+    Seq,
+    One,
+    /// Members appear in any order, and their occurrences are not counted
+    Many,
+}
+
+#[derive(Debug, Clone)]
+enum Particle {
+    Element {
+        name: String,
+        min: usize,
+        max: Option<usize>,
+    },
+    Group {
+        order: Order,
+        min: usize,
+        max: Option<usize>,
+        particles: Vec<Particle>,
+    },
+}
+
+impl Particle {
+    fn occurs(&self) -> (usize, Option<usize>) {
+        match self {
+            Particle::Element { min, max, .. } | Particle::Group { min, max, .. } => (*min, *max),
+        }
+    }
+
+    fn declares(&self, local_name: &str) -> bool {
+        match self {
+            Particle::Element { name, .. } => name == local_name,
+            Particle::Group { particles, .. } => particles
+                .iter()
+                .any(|particle| particle.declares(local_name)),
+        }
+    }
+
+    /// End positions of matching `input` from `pos`, including repetition.
+    ///
+    /// `furthest` records the end of the longest matched element, for reporting.
+    fn matches(&self, input: &[&str], pos: usize, furthest: &mut usize) -> BTreeSet<usize> {
+        let (min, max) = self.occurs();
+        let mut seen = BTreeSet::new();
+        let mut current = BTreeSet::new();
+        current.insert(pos);
+        let mut result = BTreeSet::new();
+        if min == 0 {
+            result.insert(pos);
+        }
+        let mut count = 0;
+        while !current.is_empty() && max.map_or(true, |max| count < max) {
+            count += 1;
+            let mut next = BTreeSet::new();
+            for pos in &current {
+                next.extend(self.matches_once(input, *pos, furthest));
+            }
+            if count > min {
+                next.retain(|pos| !seen.contains(pos));
+            }
+            seen.extend(next.iter().copied());
+            if count >= min {
+                result.extend(next.iter().copied());
+            }
+            current = next;
+        }
+
+        result
+    }
+
+    fn matches_once(&self, input: &[&str], pos: usize, furthest: &mut usize) -> BTreeSet<usize> {
+        let mut result = BTreeSet::new();
+        match self {
+            Particle::Element { name, .. } => {
+                if input.get(pos) == Some(&name.as_str()) {
+                    *furthest = (*furthest).max(pos + 1);
+                    result.insert(pos + 1);
+                }
+            }
+            Particle::Group {
+                order: Order::Seq,
+                particles,
+                ..
+            } => {
+                result.insert(pos);
+                for particle in particles {
+                    let mut next = BTreeSet::new();
+                    for pos in &result {
+                        next.extend(particle.matches(input, *pos, furthest));
+                    }
+                    result = next;
+                }
+            }
+            Particle::Group {
+                order: Order::One,
+                particles,
+                ..
+            } => {
+                for particle in particles {
+                    result.extend(particle.matches(input, pos, furthest));
+                }
+            }
+            Particle::Group {
+                order: Order::Many,
+                particles,
+                ..
+            } => {
+                result.insert(pos);
+                let mut current = result.clone();
+                while !current.is_empty() {
+                    let mut next = BTreeSet::new();
+                    for pos in &current {
+                        for particle in particles {
+                            next.extend(particle.matches_once(input, *pos, furthest));
+                        }
+                    }
+                    next.retain(|pos| !result.contains(pos));
+                    result.extend(next.iter().copied());
+                    current = next;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AttributeDecl {
+    name: String,
+    required: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ElementType {
+    content: Content,
+    closed: bool,
+    data_type: DataType,
+    attributes: Vec<AttributeDecl>,
+    /// `AttributeType` declared inside of `ElementType`
+    attribute_types: HashMap<String, DataType>,
+    model: Particle,
+}
+
+/// XDR schema
+#[derive(Debug, Clone)]
+pub struct Schema {
+    /// Namespace of elements, which is `name` of `Schema`
+    namespace: Option<String>,
+    attribute_types: HashMap<String, DataType>,
+    element_types: HashMap<String, ElementType>,
+}
+
+impl Schema {
+    /// Schema of manifest, which is bundled with this crate
+    pub fn bundled() -> Schema {
+        Schema::from_reader(include_str!("manifest.schema.xml").as_bytes())
+            .expect("bundled schema is valid")
+    }
+
+    /// Load XDR schema
+    pub fn from_reader<R: Read>(reader: R) -> ParseResult<Schema> {
+        let root = Element::read_document(reader)?.root;
+        let path = Path::new("schema".into());
+        if !is_xdr(&root, "Schema") {
+            return Err(root.invalid(&path, "root element must be Schema of XDR"));
+        }
+
+        let mut attribute_types = HashMap::new();
+        let mut element_types = HashMap::new();
+        for child in root.elements() {
+            if is_xdr(child, "AttributeType") {
+                attribute_types.insert(
+                    required_attribute(child, &path)?.to_string(),
+                    DataType::from_element(child),
+                );
+            } else if is_xdr(child, "ElementType") {
+                element_types.insert(
+                    required_attribute(child, &path)?.to_string(),
+                    Schema::parse_element_type(child, &path)?,
+                );
+            }
+        }
+
+        Ok(Schema {
+            namespace: root.attribute("name").map(str::to_string),
+            attribute_types,
+            element_types,
+        })
+    }
+
+    fn parse_element_type(element: &Element, path: &Path<'_>) -> ParseResult<ElementType> {
+        let content = match element.attribute("content") {
+            Some("empty") => Content::Empty,
+            Some("textOnly") => Content::TextOnly,
+            Some("eltOnly") => Content::EltOnly,
+            Some("mixed") | None => Content::Mixed,
+            Some(content) => {
+                return Err(element.invalid(path, format!("unexpected content - {}", content)))
+            }
+        };
+        let default_order = if content == Content::EltOnly {
+            Order::Seq
+        } else {
+            Order::Many
+        };
+        let mut data_type = DataType::from_element(element);
+        let mut attributes = Vec::new();
+        let mut attribute_types = HashMap::new();
+        for child in element.elements() {
+            if is_xdr(child, "attribute") {
+                attributes.push(AttributeDecl {
+                    name: child
+                        .attribute("type")
+                        .ok_or_else(|| child.invalid(path, "attribute requires type"))?
+                        .to_string(),
+                    required: child.attribute("required") == Some("yes"),
+                });
+            } else if is_xdr(child, "AttributeType") {
+                attribute_types.insert(
+                    required_attribute(child, path)?.to_string(),
+                    DataType::from_element(child),
+                );
+            } else if is_xdr(child, "datatype") {
+                data_type = DataType::from_element(child);
+            }
+        }
+
+        Ok(ElementType {
+            content,
+            closed: element.attribute("model") == Some("closed"),
+            data_type,
+            attributes,
+            attribute_types,
+            model: Particle::Group {
+                order: parse_order(element, path)?.unwrap_or(default_order),
+                min: 1,
+                max: Some(1),
+                particles: parse_particles(element, path)?,
+            },
+        })
+    }
+
+    /// Check XML document, and return all violations found, in order of position in the document.
+    ///
+    /// Error is returned only when the document is not well-formed.
+    pub fn validate<R: Read>(&self, reader: R) -> ParseResult<Vec<SchemaViolation>> {
+        let root = Element::read_document(reader)?.root;
+        let mut violations = Vec::new();
+        if self.is_target(&root) {
+            self.validate_element(&root, &mut violations);
+        } else {
+            violate(
+                &mut violations,
+                &root,
+                format!(
+                    "root element must be in namespace {}",
+                    self.namespace.as_deref().unwrap_or("(none)")
+                ),
+            );
+        }
+
+        violations.sort_by(|lhs, rhs| {
+            (lhs.line, lhs.column, &lhs.element, &lhs.detail).cmp(&(
+                rhs.line,
+                rhs.column,
+                &rhs.element,
+                &rhs.detail,
+            ))
+        });
+        Ok(violations)
+    }
+
+    fn is_target(&self, element: &Element) -> bool {
+        element.name.namespace.as_deref() == self.namespace.as_deref()
+    }
+
+    fn validate_element(&self, element: &Element, violations: &mut Vec<SchemaViolation>) {
+        let element_type = match self.element_types.get(&element.name.local_name) {
+            Some(element_type) => element_type,
+            None => {
+                violate(violations, element, "element is not declared");
+                return;
+            }
+        };
+
+        self.validate_attributes(element, element_type, violations);
+
+        let text = element.text();
+        let text = text.trim();
+        if !text.is_empty() {
+            if matches!(element_type.content, Content::Empty | Content::EltOnly) {
+                violate(violations, element, "text is not allowed");
+            } else if let Some(reason) = element_type.data_type.check(text) {
+                violate(violations, element, reason);
+            }
+        }
+
+        if matches!(element_type.content, Content::Empty | Content::TextOnly) {
+            if let Some(child) = element.elements().next() {
+                violate(violations, child, "child element is not allowed");
+            }
+            return;
+        }
+
+        let mut declared = Vec::new();
+        for child in element.elements().filter(|child| self.is_target(child)) {
+            if element_type.model.declares(&child.name.local_name) {
+                declared.push(child);
+            } else if element_type.closed {
+                violate(
+                    violations,
+                    child,
+                    format!("element is not allowed in {}", element.name.local_name),
+                );
+            }
+        }
+        let input = declared
+            .iter()
+            .map(|child| child.name.local_name.as_str())
+            .collect::<Vec<_>>();
+        let mut furthest = 0;
+        if !element_type
+            .model
+            .matches(&input, 0, &mut furthest)
+            .contains(&input.len())
+        {
+            match declared.get(furthest) {
+                Some(child) => violate(violations, child, "element is not expected here"),
+                None => violate(violations, element, "required element is missing"),
+            }
+        }
+
+        for child in declared {
+            self.validate_element(child, violations);
+        }
+    }
+
+    fn validate_attributes(
+        &self,
+        element: &Element,
+        element_type: &ElementType,
+        violations: &mut Vec<SchemaViolation>,
+    ) {
+        for declaration in element_type.attributes.iter().filter(|decl| decl.required) {
+            if element.attribute(&declaration.name).is_none() {
+                violate(
+                    violations,
+                    element,
+                    format!("required attribute {} is missing", declaration.name),
+                );
+            }
+        }
+        for attribute in element
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.name.namespace.is_none())
+        {
+            let name = attribute.name.local_name.as_str();
+            if !element_type.attributes.iter().any(|decl| decl.name == name) {
+                if element_type.closed {
+                    violate(
+                        violations,
+                        element,
+                        format!("attribute {} is not allowed", name),
+                    );
+                }
+                continue;
+            }
+            let data_type = element_type
+                .attribute_types
+                .get(name)
+                .or_else(|| self.attribute_types.get(name));
+            if let Some(reason) = data_type.and_then(|data_type| data_type.check(&attribute.value))
+            {
+                violate(
+                    violations,
+                    element,
+                    format!("unexpected value of {} - {}", name, reason),
+                );
+            }
+        }
+    }
+}
+
+fn violate<S: Into<String>>(violations: &mut Vec<SchemaViolation>, element: &Element, detail: S) {
+    violations.push(SchemaViolation {
+        line: element.position.row + 1,
+        column: element.position.column + 1,
+        element: element.name.local_name.clone(),
+        detail: detail.into(),
+    });
+}
+
+fn is_xdr(element: &Element, local_name: &str) -> bool {
+    element.name.local_name == local_name && element.name.namespace.as_deref() == Some(NS_XML_DATA)
+}
+
+fn namespaced_attribute<'a>(
+    element: &'a Element,
+    namespace: &str,
+    local_name: &str,
+) -> Option<&'a str> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| {
+            attribute.name.namespace.as_deref() == Some(namespace)
+                && attribute.name.local_name == local_name
+        })
+        .map(|attribute| attribute.value.as_str())
+}
+
+fn required_attribute<'a>(element: &'a Element, path: &Path<'_>) -> ParseResult<&'a str> {
+    element
+        .attribute("name")
+        .ok_or_else(|| element.invalid(path, format!("{} requires name", element.name.local_name)))
+}
+
+fn parse_order(element: &Element, path: &Path<'_>) -> ParseResult<Option<Order>> {
+    match element.attribute("order") {
+        Some("seq") => Ok(Some(Order::Seq)),
+        Some("one") => Ok(Some(Order::One)),
+        Some("many") => Ok(Some(Order::Many)),
+        Some(order) => Err(element.invalid(path, format!("unexpected order - {}", order))),
+        None => Ok(None),
+    }
+}
+
+fn parse_occurs(element: &Element, path: &Path<'_>) -> ParseResult<(usize, Option<usize>)> {
+    let min = match element.attribute("minOccurs") {
+        Some(min) => min
+            .parse()
+            .map_err(|_| element.invalid(path, format!("unexpected minOccurs - {}", min)))?,
+        None => 1,
+    };
+    let max = match element.attribute("maxOccurs") {
+        Some("*") => None,
+        Some(max) => Some(
+            max.parse()
+                .map_err(|_| element.invalid(path, format!("unexpected maxOccurs - {}", max)))?,
+        ),
+        None => Some(1),
+    };
+
+    Ok((min, max))
+}
+
+fn parse_particles(element: &Element, path: &Path<'_>) -> ParseResult<Vec<Particle>> {
+    let mut particles = Vec::new();
+    for child in element.elements() {
+        if is_xdr(child, "element") {
+            let (min, max) = parse_occurs(child, path)?;
+            particles.push(Particle::Element {
+                name: child
+                    .attribute("type")
+                    .ok_or_else(|| child.invalid(path, "element requires type"))?
+                    .to_string(),
+                min,
+                max,
+            });
+        } else if is_xdr(child, "group") {
+            let (min, max) = parse_occurs(child, path)?;
+            particles.push(Particle::Group {
+                order: parse_order(child, path)?.unwrap_or(Order::Seq),
+                min,
+                max,
+                particles: parse_particles(child, path)?,
+            });
+        }
+    }
+
+    Ok(particles)
+}
@@ -1,36 +1,69 @@
-use crate::debug::Path;
+use crate::{debug::Path, validate::Problem};
 use std::{borrow::Cow, io::Write};
 use xml::{writer::Error as XmlError, EventWriter};
 
 /// Error can be occured while serialization.
 ///
-/// It can be partially written, even though error is occured.
+/// Manifest is validated before writing, so nothing is written when it is invalid.
+/// Output can be partially written only when writing itself fails.
 #[derive(Debug, thiserror::Error)]
 pub enum SerializeError {
     /// Error from XmlWriter
     #[error("XmlWrite failed - {0}")]
     XmlWriteError(#[from] XmlError),
-    /// Manifest is invalid.
-    #[error("Invalid data found at {path}. {detail}")]
+    /// Manifest is invalid, with all errors found.
+    ///
+    /// Breaking change after 0.1: it used to hold `path` and `detail` of the first problem only.
+    #[error("Invalid manifest. {}", join_problems(.problems))]
     Invalid {
-        /// Path of invalid value from manifest root
-        path: String,
-        /// Detailed reason. It can be a hint to fix error.
-        detail: String,
+        /// Errors found by [`AssemblyManifest::validate`](../struct.AssemblyManifest.html#method.validate)
+        problems: Vec<Problem>,
     },
 }
 
+fn join_problems(problems: &[Problem]) -> String {
+    problems
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Serialization result
 pub type SerializeResult<R> = std::result::Result<R, SerializeError>;
 
-pub trait SerializableElement {
-    fn serialize<W: Write>(
-        &self,
-        writer: &mut EventWriter<W>,
-        path: Path<'_>,
-    ) -> SerializeResult<()>;
+/// XML writer which elements are written into
+pub type XmlWriter<'a> = EventWriter<&'a mut dyn Write>;
+
+/// Element which can be written into manifest.
+///
+/// Implement it to add an element which is not modeled by this crate,
+/// with [`Extension::Element`](../manifest/enum.Extension.html#variant.Element).
+pub trait SerializableElement: std::fmt::Debug {
+    /// Write the element. `path` is the path of the element from manifest root, for error reporting.
+    fn serialize(&self, writer: &mut XmlWriter<'_>, path: Path<'_>) -> SerializeResult<()>;
 }
 
+/// Value which can be written as attribute or text
 pub trait SerializableValue {
+    #[allow(missing_docs)]
     fn serialize(&self) -> Cow<'_, str>;
 }
+
+impl SerializableValue for bool {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Borrowed(if *self { "true" } else { "false" })
+    }
+}
+
+impl SerializableValue for u32 {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+}
+
+impl SerializableValue for u64 {
+    fn serialize(&self) -> Cow<'_, str> {
+        Cow::Owned(self.to_string())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::debug::Path;
+use std::fmt::{Display, Formatter};
+
+/// Severity of validation problem
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum Severity {
+    /// Manifest can be written, but it may not work as intended
+    Warning,
+    /// Manifest can't be written
+    Error,
+}
+
+/// Problem found by [`AssemblyManifest::validate`](../struct.AssemblyManifest.html#method.validate)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Problem {
+    #[allow(missing_docs)]
+    pub severity: Severity,
+    /// Stable identifier of the kind of problem, such as `empty-name`
+    pub code: &'static str,
+    /// Path of problematic value from manifest root, such as `.files.0.name`
+    pub path: String,
+    /// Description of the problem
+    pub message: String,
+    /// How to fix the problem
+    pub hint: &'static str,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(
+            f,
+            "{}[{}] at {}: {} ({})",
+            severity, self.code, self.path, self.message, self.hint
+        )
+    }
+}
+
+/// Collector of validation problems
+#[derive(Debug, Default)]
+pub(crate) struct Problems(Vec<Problem>);
+
+impl Problems {
+    pub fn error<S: Into<String>>(
+        &mut self,
+        path: &Path<'_>,
+        code: &'static str,
+        message: S,
+        hint: &'static str,
+    ) {
+        self.push(Severity::Error, path, code, message, hint);
+    }
+
+    pub fn warning<S: Into<String>>(
+        &mut self,
+        path: &Path<'_>,
+        code: &'static str,
+        message: S,
+        hint: &'static str,
+    ) {
+        self.push(Severity::Warning, path, code, message, hint);
+    }
+
+    fn push<S: Into<String>>(
+        &mut self,
+        severity: Severity,
+        path: &Path<'_>,
+        code: &'static str,
+        message: S,
+        hint: &'static str,
+    ) {
+        self.0.push(Problem {
+            severity,
+            code,
+            path: path.to_string(),
+            message: message.into(),
+            hint,
+        });
+    }
+
+    pub fn into_vec(self) -> Vec<Problem> {
+        self.0
+    }
+}
+
+/// Types which can check their own values.
+///
+/// Checks run before serialization, so `SerializableElement` can assume valid values.
+pub(crate) trait Validatable {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems);
+}
+
+impl<T: Validatable> Validatable for [T] {
+    fn validate(&self, path: Path<'_>, problems: &mut Problems) {
+        for (idx, item) in self.iter().enumerate() {
+            item.validate(path.appended(idx.into()), problems);
+        }
+    }
+}